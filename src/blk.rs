@@ -12,4 +12,12 @@ pub trait Block: Debug {
     ///       of the emulator, accessing persistent data after a reset may be
     ///       considered undefined behaviour.
     fn reset(&mut self) {}
+
+    /// Returns the name of this `Block`, if it has one.
+    ///
+    /// Names identify blocks to tooling such as tracers and debuggers, and
+    /// form the components of a [`Device::path`](crate::dev::Device::path).
+    fn name(&self) -> Option<&str> {
+        None
+    }
 }
@@ -1,5 +1,11 @@
+use std::fmt::Debug;
+use std::ops::Range;
+use std::rc::Rc;
+
 use crate::blk::Block;
-use crate::dev::{Device, SharedDevice};
+use crate::bus::open::OPEN;
+use crate::bus::BusError;
+use crate::dev::{self, Device, SharedDevice};
 
 /// Device bank.
 ///
@@ -9,9 +15,18 @@ use crate::dev::{Device, SharedDevice};
 /// when performing [`Device`] operations.
 ///
 /// As it is simply a wrapper, its fields are public can be accessed directly.
+///
+/// The selector may also be [bound](Bank::bind) to another device, such as a
+/// mapped control [`Register`](crate::reg::Register), allowing banks to be
+/// switched directly by the guest.
+///
+/// Selections beyond the last device are handled according to the bank's
+/// [`Select`] policy.
 #[derive(Debug, Default)]
 pub struct Bank {
     sel: usize,
+    bind: Option<Bind>,
+    policy: Select,
     banks: Vec<SharedDevice>,
 }
 
@@ -21,17 +36,61 @@ impl Bank {
         Self::default()
     }
 
+    /// Constructs a new, empty `Bank` with the provided selector policy.
+    pub fn with(policy: Select) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
+
+    /// Gets the selector policy.
+    #[must_use]
+    pub fn policy(&self) -> Select {
+        self.policy
+    }
+
+    /// Sets the selector policy.
+    pub fn set_policy(&mut self, policy: Select) {
+        self.policy = policy;
+    }
+
     /// Gets the selected device.
+    ///
+    /// This is the raw selector, before the selector policy is applied.
     #[must_use]
     pub fn get(&self) -> usize {
-        self.sel
+        match &self.bind {
+            Some(bind) => bind.get(),
+            None => self.sel,
+        }
     }
 
     /// Sets the selected device.
+    ///
+    /// Has no effect while the selector is bound.
     pub fn set(&mut self, sel: usize) {
         self.sel = sel;
     }
 
+    /// Binds the selector to the value of a device, transformed by the
+    /// provided function.
+    ///
+    /// The value is read (without side effects) as a little-endian integer
+    /// from the device's bytes each time the bank is accessed, such that
+    /// writes to the device take effect immediately. Typically, the device is
+    /// a control register mapped elsewhere on the bus, with the function
+    /// emulating the mapper's selection logic (e.g. masking off unused bits,
+    /// or remapping bank 0 to bank 1 as MBC1 does).
+    pub fn bind(&mut self, dev: SharedDevice, f: impl Fn(usize) -> usize + 'static) {
+        self.bind = Some(Bind { dev, f: Rc::new(f) });
+    }
+
+    /// Unbinds the selector, reverting to the value last [set](Bank::set).
+    pub fn unbind(&mut self) {
+        self.bind = None;
+    }
+
     /// Appends a device to the back of a bank.
     pub fn add(&mut self, dev: SharedDevice) {
         self.banks.push(dev)
@@ -53,6 +112,30 @@ impl Bank {
     pub fn remove(&mut self, index: usize) -> SharedDevice {
         self.banks.remove(index)
     }
+
+    /// Borrows the selected device, after applying the selector policy.
+    fn selected(&self) -> Option<&SharedDevice> {
+        let sel = self.get();
+        let count = self.banks.len();
+        match self.policy {
+            _ if sel < count => self.banks.get(sel),
+            _ if count == 0 => None,
+            Select::Wrap => self.banks.get(sel % count),
+            Select::Clamp => self.banks.last(),
+            Select::Open | Select::Error => None,
+        }
+    }
+
+    /// Borrows the selected device, or handles an out-of-range selection.
+    ///
+    /// Yields `Ok(None)` when the access should be treated as open-bus.
+    fn access(&self, index: usize) -> Result<Option<&SharedDevice>, BusError> {
+        match (self.selected(), self.policy) {
+            (Some(bank), _) => Ok(Some(bank)),
+            (None, Select::Open) => Ok(None),
+            (None, _) => Err(BusError::Unmapped(index)),
+        }
+    }
 }
 
 impl Block for Bank {
@@ -66,22 +149,147 @@ impl Block for Bank {
 
 impl Device for Bank {
     fn contains(&self, index: usize) -> bool {
-        match self.banks.get(self.sel) {
+        match self.selected() {
             Some(bank) => bank.borrow().contains(index),
             None => false,
         }
     }
 
     fn len(&self) -> usize {
-        self.banks[self.sel].borrow().len()
+        match self.selected() {
+            Some(bank) => bank.borrow().len(),
+            None => 0,
+        }
     }
 
-    fn read(&self, index: usize) -> u8 {
-        self.banks[self.sel].borrow().read(index)
+    /// Spans every bank, regardless of which is selected, such that the
+    /// extent remains valid as the selection changes (e.g. by the guest
+    /// writing to a bound register).
+    fn extent(&self) -> Range<usize> {
+        self.banks
+            .iter()
+            .map(|bank| bank.borrow().extent())
+            .fold(0..0, dev::union)
+    }
+
+    fn peek(&self, index: usize) -> u8 {
+        match self.access(index) {
+            Ok(Some(bank)) => bank.borrow().peek(index),
+            Ok(None) => OPEN,
+            Err(err) => panic!("`<Bank as Device>::peek()`: {err}"),
+        }
     }
 
     fn write(&mut self, index: usize, value: u8) {
-        self.banks[self.sel].borrow_mut().write(index, value);
+        match self.access(index) {
+            Ok(Some(bank)) => bank.borrow_mut().write(index, value),
+            Ok(None) => (),
+            Err(err) => panic!("`<Bank as Device>::write()`: {err}"),
+        }
+    }
+
+    fn read(&mut self, index: usize) -> u8 {
+        match self.access(index) {
+            Ok(Some(bank)) => bank.borrow_mut().read(index),
+            Ok(None) => OPEN,
+            Err(err) => panic!("`<Bank as Device>::read()`: {err}"),
+        }
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        match self.access(index) {
+            Ok(Some(bank)) => bank.borrow_mut().poke(index, value),
+            Ok(None) => (),
+            Err(err) => panic!("`<Bank as Device>::poke()`: {err}"),
+        }
+    }
+
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        match self.access(index)? {
+            Some(bank) => bank.borrow_mut().try_read(index),
+            None => Ok(OPEN),
+        }
+    }
+
+    fn try_write(&mut self, index: usize, value: u8) -> Result<(), BusError> {
+        match self.access(index)? {
+            Some(bank) => bank.borrow_mut().try_write(index, value),
+            None => Ok(()),
+        }
+    }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        match self.access(index) {
+            Ok(Some(bank)) => bank.borrow_mut().read_bytes(index, buf),
+            Ok(None) => buf.fill(OPEN),
+            Err(err) => panic!("`<Bank as Device>::read_bytes()`: {err}"),
+        }
+    }
+
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        match self.access(index) {
+            Ok(Some(bank)) => bank.borrow_mut().write_bytes(index, buf),
+            Ok(None) => (),
+            Err(err) => panic!("`<Bank as Device>::write_bytes()`: {err}"),
+        }
+    }
+
+    fn path(&self, index: usize) -> Vec<String> {
+        match self.selected() {
+            Some(bank) => dev::resolve(bank, None, index),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Bank selector policy.
+///
+/// Determines how a [`Bank`] responds when its selector lies beyond its last
+/// device, as happens when guest software writes bank numbers that real
+/// mappers would mask off.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Select {
+    /// Out-of-range selections are an error.
+    ///
+    /// Accesses panic, while their fallible counterparts return
+    /// [`BusError::Unmapped`].
+    #[default]
+    Error,
+    /// Selections wrap around modulo the number of devices.
+    Wrap,
+    /// Selections are clamped to the last device.
+    Clamp,
+    /// Out-of-range selections are open-bus.
+    ///
+    /// The bank contains no addresses, such that a [`Bus`](crate::bus::Bus)
+    /// handles accesses according to its own
+    /// [`OpenBus`](crate::bus::OpenBus) policy. When accessed directly, reads
+    /// yield `0xff` and writes are ignored.
+    Open,
+}
+
+/// Selector bound to a device.
+struct Bind {
+    dev: SharedDevice,
+    f: Rc<dyn Fn(usize) -> usize>,
+}
+
+impl Bind {
+    fn get(&self) -> usize {
+        let dev = self.dev.borrow();
+        let mut bytes = [0; std::mem::size_of::<usize>()];
+        for (index, byte) in bytes.iter_mut().enumerate().take(dev.len()) {
+            *byte = dev.peek(index);
+        }
+        (self.f)(usize::from_le_bytes(bytes))
+    }
+}
+
+impl Debug for Bind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Bind")
+            .field("dev", &self.dev)
+            .finish_non_exhaustive()
     }
 }
 
@@ -97,8 +305,10 @@ impl From<Vec<SharedDevice>> for Bank {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bus::Bus;
     use crate::dev::{Null, Random};
     use crate::mem::Ram;
+    use crate::reg::Register;
 
     fn setup() -> Bank {
         let mut bank = Bank::new();
@@ -174,4 +384,184 @@ mod tests {
             .map(|addr| bank.read(addr))
             .any(|value| value != 0xaa));
     }
+
+    #[test]
+    fn device_try_read_works() {
+        let mut bank = setup();
+        // Test bank 0
+        bank.sel = 0;
+        (0x00..=0xff).for_each(|addr| assert_eq!(bank.try_read(addr), Ok(0x55)));
+        assert_eq!(bank.try_read(0x100), Err(BusError::OutOfBounds(0x100)));
+        // Test bank 1
+        bank.sel = 1;
+        assert_eq!(bank.try_read(0x00), Err(BusError::OutOfBounds(0x00)));
+        // Test missing bank
+        bank.sel = 3;
+        assert_eq!(bank.try_read(0x00), Err(BusError::Unmapped(0x00)));
+    }
+
+    #[test]
+    fn device_try_write_works() {
+        let mut bank = setup();
+        // Test bank 0
+        bank.sel = 0;
+        (0x00..=0xff).for_each(|addr| assert_eq!(bank.try_write(addr, 0xaa), Ok(())));
+        (0x00..=0xff).for_each(|addr| assert_eq!(bank.read(addr), 0xaa));
+        // Test missing bank
+        bank.sel = 3;
+        assert_eq!(bank.try_write(0x00, 0xaa), Err(BusError::Unmapped(0x00)));
+    }
+
+    #[test]
+    fn device_read_write_word_works() {
+        let mut bank = setup();
+        // Test bank 0
+        bank.sel = 0;
+        assert_eq!(bank.read_u16_le(0x00), 0x5555);
+        bank.write_u32_be(0x10, 0x01234567);
+        assert_eq!(bank.read_u32_le(0x10), 0x67452301);
+    }
+
+    #[test]
+    fn device_peek_poke_works() {
+        let mut bank = setup();
+        // Test bank 0
+        bank.sel = 0;
+        (0x00..=0xff).for_each(|addr| assert_eq!(bank.peek(addr), 0x55));
+        (0x00..=0xff).for_each(|addr| bank.poke(addr, 0xaa));
+        (0x00..=0xff).for_each(|addr| assert_eq!(bank.read(addr), 0xaa));
+    }
+
+    #[test]
+    fn bind_works() {
+        let mut bank = Bank::new();
+        (0..4).for_each(|value| bank.add(Ram::<0x100>::from(&[value; 0x100]).to_shared()));
+        let reg = Register::<u8>::new().to_shared();
+        // Emulate MBC1-style selection
+        bank.bind(reg.clone(), |value| match value & 0x03 {
+            0 => 1,
+            sel => sel,
+        });
+        assert_eq!(bank.get(), 1);
+        assert_eq!(bank.read(0x00), 1);
+        reg.borrow_mut().write(0, 0x06);
+        assert_eq!(bank.get(), 2);
+        assert_eq!(bank.read(0x00), 2);
+        reg.borrow_mut().write(0, 0x04);
+        assert_eq!(bank.read(0x00), 1);
+        // Manual selection is ignored while bound
+        bank.set(3);
+        assert_eq!(bank.get(), 1);
+        bank.unbind();
+        assert_eq!(bank.read(0x00), 3);
+    }
+
+    #[test]
+    fn bind_bus_mapped_works() {
+        let mut bank = Bank::new();
+        (0..4).for_each(|value| bank.add(Ram::<0x100>::from(&[value; 0x100]).to_shared()));
+        let reg = Register::<u16>::new().to_shared();
+        bank.bind(reg.clone(), |value| value >> 8);
+        let mut bus = Bus::new();
+        bus.map(0x0000, bank.to_shared());
+        bus.map(0x1000, reg);
+        assert_eq!(bus.read(0x0000), 0);
+        // Switch banks from the guest
+        bus.write(0x1001, 0x02);
+        assert_eq!(bus.read(0x0000), 2);
+        bus.write_u16_le(0x1000, 0x0300);
+        assert_eq!(bus.read(0x0000), 3);
+    }
+
+    #[test]
+    fn bind_bus_paged_works() {
+        // Differently sized banks, mapped above a lower-priority RAM
+        let mut bank = Bank::new();
+        bank.add(Ram::<0x100>::from(&[0xaa; 0x100]).to_shared());
+        bank.add(Ram::<0x200>::from(&[0xbb; 0x200]).to_shared());
+        let reg = Register::<u8>::new().to_shared();
+        bank.bind(reg.clone(), |value| value);
+        let mut bus = Bus::new();
+        bus.map(0x0000, bank.to_shared());
+        bus.map(0x0000, Ram::<0x400>::from(&[0xcc; 0x400]).to_shared());
+        bus.map(0x1000, reg);
+        bus.set_page_size(Some(0x100));
+        assert_eq!(bus.read(0x0080), 0xaa);
+        assert_eq!(bus.read(0x0180), 0xcc);
+        // Switching banks from the guest must not stale the page table
+        bus.write(0x1000, 1);
+        assert_eq!(bus.read(0x0080), 0xbb);
+        assert_eq!(bus.read(0x0180), 0xbb);
+        assert_eq!(bus.read(0x0280), 0xcc);
+    }
+
+    #[test]
+    fn device_extent_works() {
+        let mut bank = setup();
+        assert_eq!(bank.extent(), 0x000..0x100);
+        bank.set(1);
+        assert_eq!(bank.extent(), 0x000..0x100);
+        bank.clear();
+        assert_eq!(bank.extent(), 0..0);
+    }
+
+    #[test]
+    fn select_error_works() {
+        let mut bank = setup();
+        bank.set(3);
+        assert!(!bank.contains(0x00));
+        assert_eq!(bank.len(), 0);
+        assert_eq!(bank.try_read(0x00), Err(BusError::Unmapped(0x00)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn select_error_panics() {
+        let mut bank = setup();
+        bank.set(3);
+        bank.read(0x00);
+    }
+
+    #[test]
+    fn select_wrap_works() {
+        let mut bank = setup();
+        bank.set_policy(Select::Wrap);
+        bank.set(3);
+        assert_eq!(bank.len(), 0x100);
+        assert_eq!(bank.read(0x00), 0x55);
+        bank.set(7);
+        assert_eq!(bank.len(), 0);
+    }
+
+    #[test]
+    fn select_clamp_works() {
+        let mut bank = Bank::with(Select::Clamp);
+        assert_eq!(bank.try_read(0x00), Err(BusError::Unmapped(0x00)));
+        bank.add(Ram::<0x100>::new().to_shared());
+        bank.add(Ram::<0x100>::from(&[0xaa; 0x100]).to_shared());
+        bank.set(0xff);
+        assert_eq!(bank.read(0x00), 0xaa);
+        bank.write(0x00, 0xbb);
+        bank.set(1);
+        assert_eq!(bank.read(0x00), 0xbb);
+    }
+
+    #[test]
+    fn select_open_works() {
+        let mut bank = setup();
+        bank.set_policy(Select::Open);
+        bank.set(3);
+        assert!(!bank.contains(0x00));
+        assert_eq!(bank.len(), 0);
+        assert_eq!(bank.read(0x00), 0xff);
+        assert_eq!(bank.try_write(0x00, 0xaa), Ok(()));
+        assert_eq!(bank.read_u16_le(0x00), 0xffff);
+        // Defers to the bus's open-bus policy when mapped
+        let shared = Rc::new(std::cell::RefCell::new(bank));
+        let mut bus = Bus::with(crate::bus::OpenBus::Fixed(0x00));
+        bus.map(0x0000, shared.clone());
+        assert_eq!(bus.read(0x0000), 0x00);
+        shared.borrow_mut().set(0);
+        assert_eq!(bus.read(0x0000), 0x55);
+    }
 }
@@ -0,0 +1,265 @@
+use crate::blk::Block;
+use crate::bus::BusError;
+use crate::dev::{self, Device, SharedDevice};
+
+/// Multi-window bank.
+///
+/// # Usage
+///
+/// The `BankedWindow` device adapter divides its address space into a series
+/// of consecutive windows, each of which independently selects a bank of the
+/// same underlying (backing) [`Device`]. Within each window, the backing
+/// device is divided into banks of the window's size, such that address
+/// `offset` of a window with selector `sel` maps to `sel * size + offset`.
+///
+/// This allows mappers that switch several independent regions of one ROM,
+/// such as the NES's MMC3, to be exposed as a single device.
+#[derive(Debug)]
+pub struct BankedWindow {
+    dev: SharedDevice,
+    windows: Vec<Window>,
+}
+
+impl BankedWindow {
+    /// Constructs a new `BankedWindow` with windows of the provided sizes.
+    ///
+    /// Each window initially selects bank 0.
+    pub fn new(dev: SharedDevice, sizes: &[usize]) -> Self {
+        Self {
+            dev,
+            windows: sizes.iter().map(|&size| Window { size, sel: 0 }).collect(),
+        }
+    }
+
+    /// Gets the number of windows.
+    #[must_use]
+    pub fn windows(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// Gets the bank selected by a window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is out of bounds.
+    #[must_use]
+    pub fn get(&self, window: usize) -> usize {
+        self.windows[window].sel
+    }
+
+    /// Sets the bank selected by a window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is out of bounds.
+    pub fn set(&mut self, window: usize, sel: usize) {
+        self.windows[window].sel = sel;
+    }
+
+    /// Locates `index`, returning its window and the translated address.
+    fn locate(&self, index: usize) -> Option<(usize, usize)> {
+        let mut offset = index;
+        for (window, &Window { size, sel }) in self.windows.iter().enumerate() {
+            if offset < size {
+                let addr = sel.checked_mul(size)?.checked_add(offset)?;
+                return Some((window, addr));
+            }
+            offset -= size;
+        }
+        None
+    }
+
+    /// Translates `index`.
+    fn translate(&self, index: usize) -> Option<usize> {
+        self.locate(index).map(|(_, addr)| addr)
+    }
+
+    /// Translates `index`, provided all of `index..index + len` lies within
+    /// the same window.
+    fn span(&self, index: usize, len: usize) -> Option<usize> {
+        let (window, addr) = self.locate(index)?;
+        let last = self.locate(index + len.saturating_sub(1))?;
+        (last.0 == window).then_some(addr)
+    }
+}
+
+impl Block for BankedWindow {
+    fn reset(&mut self) {
+        for window in &mut self.windows {
+            window.sel = 0;
+        }
+        self.dev.borrow_mut().reset();
+    }
+}
+
+impl Device for BankedWindow {
+    fn contains(&self, index: usize) -> bool {
+        match self.translate(index) {
+            Some(addr) => self.dev.borrow().contains(addr),
+            None => false,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.windows.iter().map(|window| window.size).sum()
+    }
+
+    fn peek(&self, index: usize) -> u8 {
+        match self.translate(index) {
+            Some(addr) => self.dev.borrow().peek(addr),
+            None => panic!(
+                "`<BankedWindow as Device>::peek()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        self.try_write(index, value)
+            .unwrap_or_else(|err| panic!("`<BankedWindow as Device>::write()`: {err}"));
+    }
+
+    fn read(&mut self, index: usize) -> u8 {
+        self.try_read(index)
+            .unwrap_or_else(|err| panic!("`<BankedWindow as Device>::read()`: {err}"))
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        match self.translate(index) {
+            Some(addr) => self.dev.borrow_mut().poke(addr, value),
+            None => panic!(
+                "`<BankedWindow as Device>::poke()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        let addr = self.translate(index).ok_or(BusError::OutOfBounds(index))?;
+        self.dev.borrow_mut().try_read(addr)
+    }
+
+    fn try_write(&mut self, index: usize, value: u8) -> Result<(), BusError> {
+        let addr = self.translate(index).ok_or(BusError::OutOfBounds(index))?;
+        self.dev.borrow_mut().try_write(addr, value)
+    }
+
+    /// Wide accesses within a single window are forwarded to the backing
+    /// device as-is, otherwise they are performed byte-by-byte.
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        match self.span(index, buf.len()) {
+            Some(addr) => self.dev.borrow_mut().read_bytes(addr, buf),
+            None => {
+                for (offset, byte) in buf.iter_mut().enumerate() {
+                    *byte = self.read(index + offset);
+                }
+            }
+        }
+    }
+
+    /// Wide accesses within a single window are forwarded to the backing
+    /// device as-is, otherwise they are performed byte-by-byte.
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        match self.span(index, buf.len()) {
+            Some(addr) => self.dev.borrow_mut().write_bytes(addr, buf),
+            None => {
+                for (offset, &byte) in buf.iter().enumerate() {
+                    self.write(index + offset, byte);
+                }
+            }
+        }
+    }
+
+    fn path(&self, index: usize) -> Vec<String> {
+        match self.translate(index) {
+            Some(addr) => dev::resolve(&self.dev, None, addr),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Independently selected window of a [`BankedWindow`].
+#[derive(Debug)]
+struct Window {
+    size: usize,
+    sel: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Bus;
+    use crate::mem::Rom;
+
+    fn setup() -> BankedWindow {
+        // Fill each 0x100 byte bank of the ROM with its index
+        let data: Vec<u8> = (0..0x10).flat_map(|bank| [bank; 0x100]).collect();
+        let data: [u8; 0x1000] = data.try_into().unwrap();
+        let rom = Rom::from(&data).to_shared();
+        BankedWindow::new(rom, &[0x100, 0x100, 0x200])
+    }
+
+    #[test]
+    fn new_works() {
+        let window = setup();
+        assert_eq!(window.windows(), 3);
+        (0..3).for_each(|index| assert_eq!(window.get(index), 0));
+    }
+
+    #[test]
+    fn device_contains_works() {
+        let mut window = setup();
+        (0x000..0x400).for_each(|addr| assert!(window.contains(addr)));
+        assert!(!window.contains(0x400));
+        // Out of range banks are not contained
+        window.set(2, 8);
+        assert!(!window.contains(0x200));
+    }
+
+    #[test]
+    fn device_len_works() {
+        assert_eq!(setup().len(), 0x400);
+    }
+
+    #[test]
+    fn device_read_works() {
+        let mut window = setup();
+        window.set(0, 3);
+        window.set(1, 7);
+        window.set(2, 2);
+        assert_eq!(window.read(0x000), 3);
+        assert_eq!(window.read(0x0ff), 3);
+        assert_eq!(window.read(0x100), 7);
+        // Larger windows select larger banks
+        assert_eq!(window.read(0x200), 4);
+        assert_eq!(window.read(0x3ff), 5);
+        assert_eq!(window.try_read(0x400), Err(BusError::OutOfBounds(0x400)));
+    }
+
+    #[test]
+    fn device_read_word_works() {
+        let mut window = setup();
+        window.set(0, 1);
+        window.set(1, 2);
+        assert_eq!(window.read_u16_le(0x0fe), 0x0101);
+        assert_eq!(window.read_u16_le(0x0ff), 0x0201);
+    }
+
+    #[test]
+    fn block_reset_works() {
+        let mut window = setup();
+        window.set(1, 4);
+        window.reset();
+        assert_eq!(window.get(1), 0);
+    }
+
+    #[test]
+    fn bus_mapped_works() {
+        let mut window = setup();
+        window.set(1, 9);
+        let mut bus = Bus::new();
+        bus.map(0x8000, window.to_shared());
+        assert_eq!(bus.read(0x8100), 9);
+        assert_eq!(bus.len(), 0x400);
+    }
+}
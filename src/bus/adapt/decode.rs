@@ -0,0 +1,271 @@
+use std::fmt::Debug;
+use std::rc::Rc;
+
+use crate::blk::Block;
+use crate::bus::BusError;
+use crate::dev::{self, Device, SharedDevice};
+
+/// Address decoder.
+///
+/// # Usage
+///
+/// The `Decode` adapter emulates chip-select logic, selecting the underlying
+/// [`Device`] only for addresses where `index & mask == select`. Selected
+/// addresses are forwarded with the decoded bits cleared (`index & !mask`), or
+/// through a custom translation provided with [`Decode::translate`].
+///
+/// This allows incompletely decoded hardware to be described directly, rather
+/// than by enumerating each of its mirrors. The decoder spans the address
+/// space up to and including the mask's most significant bit. As such, it is
+/// typically mapped at the base of a [`Bus`](crate::bus::Bus).
+#[derive(Clone)]
+pub struct Decode {
+    mask: usize,
+    select: usize,
+    dev: SharedDevice,
+    translate: Option<Rc<dyn Fn(usize) -> usize>>,
+}
+
+impl Decode {
+    /// Constructs a new `Decode`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mask` is zero, or if `select` has bits set outside of
+    /// `mask`.
+    pub fn new(mask: usize, select: usize, dev: SharedDevice) -> Self {
+        assert!(mask != 0, "decoder mask must be non-zero");
+        assert!(select & !mask == 0, "decoder select must be within mask");
+        Self {
+            mask,
+            select,
+            dev,
+            translate: None,
+        }
+    }
+
+    /// Forwards selected addresses through the provided translation, rather
+    /// than clearing the decoded bits.
+    #[must_use]
+    pub fn translate(mut self, f: impl Fn(usize) -> usize + 'static) -> Self {
+        self.translate = Some(Rc::new(f));
+        self
+    }
+
+    /// Decodes `index`, returning the forwarded address if it is selected.
+    fn decode(&self, index: usize) -> Option<usize> {
+        if index >= self.len() || index & self.mask != self.select {
+            return None;
+        }
+        Some(match &self.translate {
+            Some(f) => f(index),
+            None => index & !self.mask,
+        })
+    }
+
+    /// Decodes `index`, provided all of `index..index + len` is selected and
+    /// forwarded contiguously.
+    fn span(&self, index: usize, len: usize) -> Option<usize> {
+        let addr = self.decode(index)?;
+        (1..len)
+            .all(|offset| self.decode(index + offset) == Some(addr + offset))
+            .then_some(addr)
+    }
+}
+
+impl Debug for Decode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Decode")
+            .field("mask", &self.mask)
+            .field("select", &self.select)
+            .field("dev", &self.dev)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Block for Decode {
+    fn reset(&mut self) {
+        self.dev.borrow_mut().reset();
+    }
+}
+
+impl Device for Decode {
+    fn contains(&self, index: usize) -> bool {
+        match self.decode(index) {
+            Some(addr) => self.dev.borrow().contains(addr),
+            None => false,
+        }
+    }
+
+    fn len(&self) -> usize {
+        let bits = usize::BITS - self.mask.leading_zeros();
+        1usize.checked_shl(bits).unwrap_or(usize::MAX)
+    }
+
+    fn peek(&self, index: usize) -> u8 {
+        match self.decode(index) {
+            Some(addr) => self.dev.borrow().peek(addr),
+            None => panic!(
+                "`<Decode as Device>::peek()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        self.try_write(index, value)
+            .unwrap_or_else(|err| panic!("`<Decode as Device>::write()`: {err}"));
+    }
+
+    fn read(&mut self, index: usize) -> u8 {
+        self.try_read(index)
+            .unwrap_or_else(|err| panic!("`<Decode as Device>::read()`: {err}"))
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        match self.decode(index) {
+            Some(addr) => self.dev.borrow_mut().poke(addr, value),
+            None => panic!(
+                "`<Decode as Device>::poke()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        let addr = self.decode(index).ok_or(BusError::OutOfBounds(index))?;
+        self.dev.borrow_mut().try_read(addr)
+    }
+
+    fn try_write(&mut self, index: usize, value: u8) -> Result<(), BusError> {
+        let addr = self.decode(index).ok_or(BusError::OutOfBounds(index))?;
+        self.dev.borrow_mut().try_write(addr, value)
+    }
+
+    /// Wide accesses forwarded contiguously are passed to the device as-is,
+    /// otherwise they are performed byte-by-byte.
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        match self.span(index, buf.len()) {
+            Some(addr) => self.dev.borrow_mut().read_bytes(addr, buf),
+            None => {
+                for (offset, byte) in buf.iter_mut().enumerate() {
+                    *byte = self.read(index + offset);
+                }
+            }
+        }
+    }
+
+    /// Wide accesses forwarded contiguously are passed to the device as-is,
+    /// otherwise they are performed byte-by-byte.
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        match self.span(index, buf.len()) {
+            Some(addr) => self.dev.borrow_mut().write_bytes(addr, buf),
+            None => {
+                for (offset, &byte) in buf.iter().enumerate() {
+                    self.write(index + offset, byte);
+                }
+            }
+        }
+    }
+
+    fn path(&self, index: usize) -> Vec<String> {
+        match self.decode(index) {
+            Some(addr) => dev::resolve(&self.dev, None, addr),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Bus;
+    use crate::mem::Ram;
+
+    fn setup() -> Decode {
+        // Select A15..A13 == 0b011 (i.e. 0x6000..0x8000)
+        let ram = Ram::<0x2000>::new().to_shared();
+        Decode::new(0xe000, 0x6000, ram)
+    }
+
+    #[test]
+    fn new_works() {
+        let _ = setup();
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_panics() {
+        let ram = Ram::<0x2000>::new().to_shared();
+        let _ = Decode::new(0xe000, 0x1000, ram);
+    }
+
+    #[test]
+    fn device_contains_works() {
+        let decode = setup();
+        (0x0000..0x6000).for_each(|addr| assert!(!decode.contains(addr)));
+        (0x6000..0x8000).for_each(|addr| assert!(decode.contains(addr)));
+        (0x8000..0x10000).for_each(|addr| assert!(!decode.contains(addr)));
+        assert!(!decode.contains(0x16000));
+    }
+
+    #[test]
+    fn device_len_works() {
+        assert_eq!(setup().len(), 0x10000);
+    }
+
+    #[test]
+    fn device_read_write_works() {
+        let mut decode = setup();
+        decode.write(0x6010, 0xaa);
+        assert_eq!(decode.read(0x6010), 0xaa);
+        assert_eq!(decode.try_read(0x0010), Err(BusError::OutOfBounds(0x0010)));
+        assert_eq!(
+            decode.try_write(0xe010, 0xaa),
+            Err(BusError::OutOfBounds(0xe010))
+        );
+    }
+
+    #[test]
+    fn device_read_write_word_works() {
+        let mut decode = setup();
+        decode.write_u16_le(0x6100, 0x1234);
+        assert_eq!(decode.read_u16_be(0x6100), 0x3412);
+    }
+
+    #[test]
+    fn translate_works() {
+        // Partially decode A11..A0, mirroring 2 KiB of RAM
+        let ram = Ram::<0x800>::new().to_shared();
+        let mut decode = Decode::new(0xe000, 0x0000, ram).translate(|index| index & 0x7ff);
+        decode.write(0x0001, 0xaa);
+        assert_eq!(decode.read(0x0801), 0xaa);
+        assert_eq!(decode.read(0x1801), 0xaa);
+        // Wide accesses are split when not contiguous
+        decode.write_u16_le(0x07ff, 0x1234);
+        assert_eq!(decode.read(0x0000), 0x12);
+    }
+
+    #[test]
+    fn bus_mapped_works() {
+        let mut bus = Bus::new();
+        let rom = Ram::<0x100>::from(&[0x55; 0x100]).to_shared();
+        let ram = Ram::<0x100>::from(&[0xaa; 0x100]).to_shared();
+        // Select on A15 only
+        bus.map(
+            0x0000,
+            Decode::new(0x8000, 0x0000, rom)
+                .translate(|index| index & 0xff)
+                .to_shared(),
+        );
+        bus.map(
+            0x0000,
+            Decode::new(0x8000, 0x8000, ram)
+                .translate(|index| index & 0xff)
+                .to_shared(),
+        );
+        assert_eq!(bus.read(0x1234), 0x55);
+        assert_eq!(bus.read(0x8234), 0xaa);
+        assert_eq!(bus.read(0xff00), 0xaa);
+    }
+}
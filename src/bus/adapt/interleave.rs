@@ -0,0 +1,329 @@
+use crate::blk::Block;
+use crate::bus::BusError;
+use crate::dev::{self, Device, SharedDevice};
+
+/// Interleaved devices.
+///
+/// # Usage
+///
+/// The `Interleave` adapter combines several devices into a single
+/// byte-addressable device, alternating between them every `stride` bytes.
+/// For example, a 16-bit ROM stored as two 8-bit chips holding its even and
+/// odd bytes can be combined with a stride of 1.
+///
+/// Its inverse is [`Deinterleave`].
+#[derive(Debug)]
+pub struct Interleave {
+    stride: usize,
+    devs: Vec<SharedDevice>,
+}
+
+impl Interleave {
+    /// Constructs a new `Interleave`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    pub fn new(stride: usize, devs: Vec<SharedDevice>) -> Self {
+        assert!(stride != 0, "interleave stride must be non-zero");
+        Self { stride, devs }
+    }
+
+    /// Translates `index` into a device and its address.
+    fn translate(&self, index: usize) -> Option<(&SharedDevice, usize)> {
+        if self.devs.is_empty() {
+            return None;
+        }
+        let chunk = index / self.stride;
+        let dev = &self.devs[chunk % self.devs.len()];
+        let addr = chunk / self.devs.len() * self.stride + index % self.stride;
+        Some((dev, addr))
+    }
+}
+
+impl Block for Interleave {
+    fn reset(&mut self) {
+        for dev in &self.devs {
+            dev.borrow_mut().reset();
+        }
+    }
+}
+
+impl Device for Interleave {
+    fn contains(&self, index: usize) -> bool {
+        match self.translate(index) {
+            Some((dev, addr)) => dev.borrow().contains(addr),
+            None => false,
+        }
+    }
+
+    /// The combined length is limited by the shortest device.
+    fn len(&self) -> usize {
+        let len = self.devs.iter().map(|dev| dev.borrow().len()).min();
+        len.unwrap_or_default() * self.devs.len()
+    }
+
+    fn peek(&self, index: usize) -> u8 {
+        match self.translate(index) {
+            Some((dev, addr)) => dev.borrow().peek(addr),
+            None => panic!(
+                "`<Interleave as Device>::peek()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        self.try_write(index, value)
+            .unwrap_or_else(|err| panic!("`<Interleave as Device>::write()`: {err}"));
+    }
+
+    fn read(&mut self, index: usize) -> u8 {
+        self.try_read(index)
+            .unwrap_or_else(|err| panic!("`<Interleave as Device>::read()`: {err}"))
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        match self.translate(index) {
+            Some((dev, addr)) => dev.borrow_mut().poke(addr, value),
+            None => panic!(
+                "`<Interleave as Device>::poke()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        let (dev, addr) = self.translate(index).ok_or(BusError::OutOfBounds(index))?;
+        dev.borrow_mut().try_read(addr)
+    }
+
+    fn try_write(&mut self, index: usize, value: u8) -> Result<(), BusError> {
+        let (dev, addr) = self.translate(index).ok_or(BusError::OutOfBounds(index))?;
+        dev.borrow_mut().try_write(addr, value)
+    }
+
+    fn path(&self, index: usize) -> Vec<String> {
+        match self.translate(index) {
+            Some((dev, addr)) => dev::resolve(dev, None, addr),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Deinterleaved device.
+///
+/// # Usage
+///
+/// The `Deinterleave` adapter presents a single lane of an interleaved
+/// device, selecting every `lanes`th run of `stride` bytes, starting from the
+/// run at position `lane`. For example, the even bytes of a 16-bit ROM can be
+/// viewed as an 8-bit chip with a stride of 1, 2 lanes, selecting lane 0.
+///
+/// Its inverse is [`Interleave`].
+#[derive(Debug)]
+pub struct Deinterleave {
+    stride: usize,
+    lanes: usize,
+    lane: usize,
+    dev: SharedDevice,
+}
+
+impl Deinterleave {
+    /// Constructs a new `Deinterleave`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero, or if `lane` is not less than `lanes`.
+    pub fn new(stride: usize, lanes: usize, lane: usize, dev: SharedDevice) -> Self {
+        assert!(stride != 0, "deinterleave stride must be non-zero");
+        assert!(lane < lanes, "deinterleave lane out of range");
+        Self {
+            stride,
+            lanes,
+            lane,
+            dev,
+        }
+    }
+
+    /// Translates `index` into the underlying device.
+    fn translate(&self, index: usize) -> Option<usize> {
+        let chunk = index / self.stride;
+        chunk
+            .checked_mul(self.lanes)?
+            .checked_add(self.lane)?
+            .checked_mul(self.stride)?
+            .checked_add(index % self.stride)
+    }
+}
+
+impl Block for Deinterleave {
+    fn reset(&mut self) {
+        self.dev.borrow_mut().reset();
+    }
+}
+
+impl Device for Deinterleave {
+    fn contains(&self, index: usize) -> bool {
+        match self.translate(index) {
+            Some(addr) => self.dev.borrow().contains(addr),
+            None => false,
+        }
+    }
+
+    fn len(&self) -> usize {
+        let len = self.dev.borrow().len();
+        let group = self.stride * self.lanes;
+        let rem = (len % group).saturating_sub(self.lane * self.stride);
+        len / group * self.stride + rem.min(self.stride)
+    }
+
+    fn peek(&self, index: usize) -> u8 {
+        match self.translate(index) {
+            Some(addr) => self.dev.borrow().peek(addr),
+            None => panic!(
+                "`<Deinterleave as Device>::peek()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        self.try_write(index, value)
+            .unwrap_or_else(|err| panic!("`<Deinterleave as Device>::write()`: {err}"));
+    }
+
+    fn read(&mut self, index: usize) -> u8 {
+        self.try_read(index)
+            .unwrap_or_else(|err| panic!("`<Deinterleave as Device>::read()`: {err}"))
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        match self.translate(index) {
+            Some(addr) => self.dev.borrow_mut().poke(addr, value),
+            None => panic!(
+                "`<Deinterleave as Device>::poke()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        let addr = self.translate(index).ok_or(BusError::OutOfBounds(index))?;
+        self.dev.borrow_mut().try_read(addr)
+    }
+
+    fn try_write(&mut self, index: usize, value: u8) -> Result<(), BusError> {
+        let addr = self.translate(index).ok_or(BusError::OutOfBounds(index))?;
+        self.dev.borrow_mut().try_write(addr, value)
+    }
+
+    fn path(&self, index: usize) -> Vec<String> {
+        match self.translate(index) {
+            Some(addr) => dev::resolve(&self.dev, None, addr),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Bus;
+    use crate::mem::{Ram, Rom};
+
+    fn setup() -> Interleave {
+        let even = Rom::<0x100>::from(&[0xee; 0x100]).to_shared();
+        let odd = Rom::<0x100>::from(&[0x0d; 0x100]).to_shared();
+        Interleave::new(1, vec![even, odd])
+    }
+
+    #[test]
+    fn new_works() {
+        let _ = setup();
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_panics() {
+        let _ = Interleave::new(0, Vec::new());
+    }
+
+    #[test]
+    fn device_contains_works() {
+        let interleave = setup();
+        (0x000..0x200).for_each(|addr| assert!(interleave.contains(addr)));
+        assert!(!interleave.contains(0x200));
+        assert!(!Interleave::new(1, Vec::new()).contains(0));
+    }
+
+    #[test]
+    fn device_len_works() {
+        assert_eq!(setup().len(), 0x200);
+        assert_eq!(Interleave::new(1, Vec::new()).len(), 0);
+    }
+
+    #[test]
+    fn device_read_works() {
+        let mut interleave = setup();
+        assert_eq!(interleave.read(0x000), 0xee);
+        assert_eq!(interleave.read(0x001), 0x0d);
+        assert_eq!(interleave.read(0x1fe), 0xee);
+        assert_eq!(interleave.read_u16_be(0x010), 0xee0d);
+        assert_eq!(
+            interleave.try_read(0x200),
+            Err(BusError::OutOfBounds(0x100))
+        );
+    }
+
+    #[test]
+    fn device_write_works() {
+        let lo = Ram::<0x100>::new().to_shared();
+        let hi = Ram::<0x100>::new().to_shared();
+        let mut interleave = Interleave::new(2, vec![lo.clone(), hi.clone()]);
+        interleave.write_u32_be(0x000, 0x01234567);
+        interleave.write_u32_be(0x004, 0x89abcdef);
+        assert_eq!(lo.borrow_mut().read_u32_be(0x000), 0x012389ab);
+        assert_eq!(hi.borrow_mut().read_u32_be(0x000), 0x4567cdef);
+    }
+
+    #[test]
+    fn deinterleave_works() {
+        let data: Vec<u8> = (0..=0xff).collect();
+        let data: [u8; 0x100] = data.try_into().unwrap();
+        let rom = Rom::from(&data).to_shared();
+        let mut even = Deinterleave::new(1, 2, 0, rom.clone());
+        let mut odd = Deinterleave::new(1, 2, 1, rom.clone());
+        assert_eq!(even.len(), 0x80);
+        assert_eq!(odd.len(), 0x80);
+        assert_eq!(even.read(0x10), 0x20);
+        assert_eq!(odd.read(0x10), 0x21);
+        assert!(!odd.contains(0x80));
+        // Check uneven lengths
+        let dev = Ram::<0x7>::new().to_shared();
+        assert_eq!(Deinterleave::new(2, 2, 0, dev.clone()).len(), 0x4);
+        assert_eq!(Deinterleave::new(2, 2, 1, dev.clone()).len(), 0x3);
+        assert_eq!(Deinterleave::new(2, 4, 3, dev).len(), 0x1);
+    }
+
+    #[test]
+    fn round_trip_works() {
+        let data: Vec<u8> = (0..=0xff).collect();
+        let data: [u8; 0x100] = data.try_into().unwrap();
+        let rom = Rom::from(&data).to_shared();
+        let lanes = (0..4)
+            .map(|lane| Deinterleave::new(2, 4, lane, rom.clone()).to_shared())
+            .collect();
+        let mut interleave = Interleave::new(2, lanes);
+        assert_eq!(interleave.len(), 0x100);
+        (0x00..=0xff).for_each(|addr| assert_eq!(interleave.read(addr), addr as u8));
+    }
+
+    #[test]
+    fn bus_mapped_works() {
+        let mut bus = Bus::new();
+        bus.map(0x1000, setup().to_shared());
+        assert_eq!(bus.len(), 0x200);
+        assert_eq!(bus.read_u16_le(0x1100), 0x0dee);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::blk::Block;
+use crate::bus::BusError;
+use crate::dev::{self, Device, SharedDevice};
+
+/// Address mirror.
+///
+/// # Usage
+///
+/// The `Mirror` adapter presents the underlying [`Device`] repeatedly across a
+/// larger window, with addresses wrapping around every `dev.len()` bytes.
+///
+/// This is commonly used to emulate partially decoded memory, such as the
+/// NES's 2 KiB of internal RAM mirrored across `0x0000..0x2000`.
+#[derive(Debug)]
+pub struct Mirror {
+    len: usize,
+    dev: SharedDevice,
+}
+
+impl Mirror {
+    /// Constructs a new `Mirror` spanning `len` bytes.
+    pub fn new(len: usize, dev: SharedDevice) -> Self {
+        Self { len, dev }
+    }
+
+    /// Translates `index` into the underlying device, provided it lies within
+    /// the window.
+    fn translate(&self, index: usize) -> Option<usize> {
+        match self.dev.borrow().len() {
+            0 => None,
+            size => (index < self.len).then_some(index % size),
+        }
+    }
+
+    /// Splits `index..index + len` into the translated chunks which do not
+    /// wrap around, as tuples of `(addr, offset, len)`.
+    fn chunks(&self, index: usize, len: usize) -> Result<Vec<(usize, usize, usize)>, BusError> {
+        let size = self.dev.borrow().len();
+        let mut chunks = Vec::new();
+        let mut offset = 0;
+        while offset < len {
+            let addr = self
+                .translate(index + offset)
+                .ok_or(BusError::OutOfBounds(index + offset))?;
+            let count = (size - addr).min(len - offset);
+            chunks.push((addr, offset, count));
+            offset += count;
+        }
+        Ok(chunks)
+    }
+}
+
+impl Block for Mirror {
+    fn reset(&mut self) {
+        self.dev.borrow_mut().reset();
+    }
+}
+
+impl Device for Mirror {
+    fn contains(&self, index: usize) -> bool {
+        match self.translate(index) {
+            Some(addr) => self.dev.borrow().contains(addr),
+            None => false,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn peek(&self, index: usize) -> u8 {
+        match self.translate(index) {
+            Some(addr) => self.dev.borrow().peek(addr),
+            None => panic!(
+                "`<Mirror as Device>::peek()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        self.try_write(index, value)
+            .unwrap_or_else(|err| panic!("`<Mirror as Device>::write()`: {err}"));
+    }
+
+    fn read(&mut self, index: usize) -> u8 {
+        self.try_read(index)
+            .unwrap_or_else(|err| panic!("`<Mirror as Device>::read()`: {err}"))
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        match self.translate(index) {
+            Some(addr) => self.dev.borrow_mut().poke(addr, value),
+            None => panic!(
+                "`<Mirror as Device>::poke()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        let addr = self.translate(index).ok_or(BusError::OutOfBounds(index))?;
+        self.dev.borrow_mut().try_read(addr)
+    }
+
+    fn try_write(&mut self, index: usize, value: u8) -> Result<(), BusError> {
+        let addr = self.translate(index).ok_or(BusError::OutOfBounds(index))?;
+        self.dev.borrow_mut().try_write(addr, value)
+    }
+
+    /// Wide accesses which wrap around are split at the boundary.
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        let chunks = self
+            .chunks(index, buf.len())
+            .unwrap_or_else(|err| panic!("`<Mirror as Device>::read_bytes()`: {err}"));
+        for (addr, offset, len) in chunks {
+            self.dev
+                .borrow_mut()
+                .read_bytes(addr, &mut buf[offset..offset + len]);
+        }
+    }
+
+    /// Wide accesses which wrap around are split at the boundary.
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        let chunks = self
+            .chunks(index, buf.len())
+            .unwrap_or_else(|err| panic!("`<Mirror as Device>::write_bytes()`: {err}"));
+        for (addr, offset, len) in chunks {
+            self.dev
+                .borrow_mut()
+                .write_bytes(addr, &buf[offset..offset + len]);
+        }
+    }
+
+    fn path(&self, index: usize) -> Vec<String> {
+        match self.translate(index) {
+            Some(addr) => dev::resolve(&self.dev, None, addr),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Bus;
+    use crate::dev::Null;
+    use crate::mem::Ram;
+
+    fn setup() -> Mirror {
+        let ram = Ram::<0x100>::new().to_shared();
+        Mirror::new(0x400, ram)
+    }
+
+    #[test]
+    fn new_works() {
+        let _ = setup();
+    }
+
+    #[test]
+    fn device_contains_works() {
+        let mirror = setup();
+        (0x000..0x400).for_each(|addr| assert!(mirror.contains(addr)));
+        (0x400..0x500).for_each(|addr| assert!(!mirror.contains(addr)));
+        // Empty devices are never contained
+        let mirror = Mirror::new(0x400, Null::<0>::new().to_shared());
+        (0x000..0x400).for_each(|addr| assert!(!mirror.contains(addr)));
+    }
+
+    #[test]
+    fn device_len_works() {
+        assert_eq!(setup().len(), 0x400);
+    }
+
+    #[test]
+    fn device_read_write_works() {
+        let mut mirror = setup();
+        mirror.write(0x010, 0xaa);
+        mirror.write(0x3ff, 0xbb);
+        (0x000..0x400).step_by(0x100).for_each(|base| {
+            assert_eq!(mirror.read(base + 0x10), 0xaa);
+            assert_eq!(mirror.read(base + 0xff), 0xbb);
+        });
+    }
+
+    #[test]
+    fn device_try_read_write_works() {
+        let mut mirror = setup();
+        assert_eq!(mirror.try_write(0x123, 0xaa), Ok(()));
+        assert_eq!(mirror.try_read(0x023), Ok(0xaa));
+        assert_eq!(mirror.try_read(0x400), Err(BusError::OutOfBounds(0x400)));
+        assert_eq!(
+            mirror.try_write(0x400, 0xaa),
+            Err(BusError::OutOfBounds(0x400))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn device_read_panics() {
+        let mut mirror = setup();
+        mirror.read(0x400);
+    }
+
+    #[test]
+    fn device_read_write_word_works() {
+        let mut mirror = setup();
+        // Wide accesses wrap around the boundary
+        mirror.write_u16_be(0x1ff, 0x1234);
+        assert_eq!(mirror.read(0x0ff), 0x12);
+        assert_eq!(mirror.read(0x000), 0x34);
+        assert_eq!(mirror.read_u32_le(0x2fe), 0x00341200);
+    }
+
+    #[test]
+    fn device_peek_poke_works() {
+        let mut mirror = setup();
+        mirror.poke(0x301, 0xaa);
+        assert_eq!(mirror.peek(0x001), 0xaa);
+        assert_eq!(mirror.peek(0x101), 0xaa);
+    }
+
+    #[test]
+    fn bus_mapped_works() {
+        let ram = Ram::<0x800>::new().to_shared();
+        let mut bus = Bus::new();
+        bus.map(0x0000, Mirror::new(0x2000, ram).to_shared());
+        bus.map(0x2000, Ram::<0x8>::new().to_shared());
+        assert_eq!(bus.len(), 0x2008);
+        assert!(bus.contains(0x1fff));
+        bus.write(0x0001, 0xaa);
+        assert_eq!(bus.read(0x0801), 0xaa);
+        assert_eq!(bus.read(0x1801), 0xaa);
+    }
+}
@@ -11,10 +11,26 @@
 //! devices they modify through a [`SharedDevice`](crate::dev::SharedDevice),
 //! allowing for sharing and reuse elsewhere.
 
-pub use self::bank::Bank;
+pub use self::bank::{Bank, Select};
+pub use self::banked::BankedWindow;
+pub use self::decode::Decode;
+pub use self::interleave::{Deinterleave, Interleave};
+pub use self::mirror::Mirror;
+pub use self::overlay::Overlay;
+pub use self::protect::{Protect, ReadOnly, WriteOnly};
 pub use self::remap::Remap;
+pub use self::split::Split;
+pub use self::trace::{Record, Trace};
 pub use self::view::View;
 
 mod bank;
+mod banked;
+mod decode;
+mod interleave;
+mod mirror;
+mod overlay;
+mod protect;
 mod remap;
+mod split;
+mod trace;
 mod view;
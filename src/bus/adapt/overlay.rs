@@ -0,0 +1,258 @@
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::ops::Range;
+use std::rc::Rc;
+
+use crate::blk::Block;
+use crate::bus::BusError;
+use crate::dev::{self, Device, SharedDevice};
+use crate::reg::Register;
+
+/// Switchable overlay.
+///
+/// # Usage
+///
+/// The `Overlay` adapter places a top device atop a bottom device. While
+/// enabled, accesses to addresses contained by the top device are routed to
+/// it, with all others falling through to the bottom device.
+///
+/// The overlay is enabled according to the value of a control [`Register`],
+/// which may itself be mapped onto a [`Bus`](crate::bus::Bus). By default, it
+/// is enabled while the register holds zero. This allows, for example, a boot
+/// ROM to be overlaid atop cartridge space until the guest writes to the
+/// control register, without any intervention from the CPU model.
+pub struct Overlay {
+    top: SharedDevice,
+    bottom: SharedDevice,
+    ctrl: Rc<RefCell<Register<u8>>>,
+    enable: Rc<dyn Fn(u8) -> bool>,
+}
+
+impl Overlay {
+    /// Constructs a new `Overlay`, controlled by the provided register.
+    pub fn new(top: SharedDevice, bottom: SharedDevice, ctrl: Rc<RefCell<Register<u8>>>) -> Self {
+        Self {
+            top,
+            bottom,
+            ctrl,
+            enable: Rc::new(|ctrl| ctrl == 0),
+        }
+    }
+
+    /// Enables the overlay only while the control register's value satisfies
+    /// the provided predicate.
+    #[must_use]
+    pub fn when(mut self, enable: impl Fn(u8) -> bool + 'static) -> Self {
+        self.enable = Rc::new(enable);
+        self
+    }
+
+    /// Checks if the overlay is enabled.
+    #[must_use]
+    pub fn enabled(&self) -> bool {
+        (self.enable)(**self.ctrl.borrow())
+    }
+
+    /// Selects the device which decodes `index`.
+    fn select(&self, index: usize) -> &SharedDevice {
+        if self.enabled() && self.top.borrow().contains(index) {
+            &self.top
+        } else {
+            &self.bottom
+        }
+    }
+
+    /// Selects the device which decodes `index`, provided every address in
+    /// `index..index + len` is decoded to it.
+    fn span(&self, index: usize, len: usize) -> Option<&SharedDevice> {
+        let dev = self.select(index);
+        (index + 1..index + len)
+            .all(|index| Rc::ptr_eq(self.select(index), dev))
+            .then_some(dev)
+    }
+}
+
+impl Debug for Overlay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Overlay")
+            .field("top", &self.top)
+            .field("bottom", &self.bottom)
+            .field("ctrl", &self.ctrl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Block for Overlay {
+    fn reset(&mut self) {
+        self.top.borrow_mut().reset();
+        self.bottom.borrow_mut().reset();
+        self.ctrl.borrow_mut().reset();
+    }
+}
+
+impl Device for Overlay {
+    fn contains(&self, index: usize) -> bool {
+        self.select(index).borrow().contains(index)
+    }
+
+    fn len(&self) -> usize {
+        self.top.borrow().len().max(self.bottom.borrow().len())
+    }
+
+    fn extent(&self) -> Range<usize> {
+        dev::union(self.top.borrow().extent(), self.bottom.borrow().extent())
+    }
+
+    fn peek(&self, index: usize) -> u8 {
+        self.select(index).borrow().peek(index)
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        self.select(index).borrow_mut().write(index, value);
+    }
+
+    fn read(&mut self, index: usize) -> u8 {
+        self.select(index).borrow_mut().read(index)
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        self.select(index).borrow_mut().poke(index, value);
+    }
+
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        self.select(index).borrow_mut().try_read(index)
+    }
+
+    fn try_write(&mut self, index: usize, value: u8) -> Result<(), BusError> {
+        self.select(index).borrow_mut().try_write(index, value)
+    }
+
+    /// Wide accesses decoded entirely to a single device are forwarded to it
+    /// as-is, otherwise they are performed byte-by-byte.
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        match self.span(index, buf.len()) {
+            Some(dev) => dev.borrow_mut().read_bytes(index, buf),
+            None => {
+                for (offset, byte) in buf.iter_mut().enumerate() {
+                    *byte = self.read(index + offset);
+                }
+            }
+        }
+    }
+
+    /// Wide accesses decoded entirely to a single device are forwarded to it
+    /// as-is, otherwise they are performed byte-by-byte.
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        match self.span(index, buf.len()) {
+            Some(dev) => dev.borrow_mut().write_bytes(index, buf),
+            None => {
+                for (offset, &byte) in buf.iter().enumerate() {
+                    self.write(index + offset, byte);
+                }
+            }
+        }
+    }
+
+    fn path(&self, index: usize) -> Vec<String> {
+        dev::resolve(self.select(index), None, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Bus;
+    use crate::mem::{Ram, Rom};
+
+    fn setup() -> (Rc<RefCell<Register<u8>>>, Overlay) {
+        let boot = Rom::<0x100>::from(&[0xbb; 0x100]).to_shared();
+        let cart = Ram::<0x400>::from(&[0xcc; 0x400]).to_shared();
+        let ctrl = Rc::new(RefCell::new(Register::<u8>::new()));
+        (ctrl.clone(), Overlay::new(boot, cart, ctrl))
+    }
+
+    #[test]
+    fn new_works() {
+        let _ = setup();
+    }
+
+    #[test]
+    fn device_contains_works() {
+        let (ctrl, overlay) = setup();
+        (0x000..0x400).for_each(|addr| assert!(overlay.contains(addr)));
+        ctrl.borrow_mut().write(0, 1);
+        (0x000..0x400).for_each(|addr| assert!(overlay.contains(addr)));
+        assert!(!overlay.contains(0x400));
+    }
+
+    #[test]
+    fn device_len_works() {
+        let (ctrl, overlay) = setup();
+        assert_eq!(overlay.len(), 0x400);
+        ctrl.borrow_mut().write(0, 1);
+        assert_eq!(overlay.len(), 0x400);
+    }
+
+    #[test]
+    fn device_read_works() {
+        let (ctrl, mut overlay) = setup();
+        // Enabled
+        assert!(overlay.enabled());
+        assert_eq!(overlay.read(0x0ff), 0xbb);
+        assert_eq!(overlay.read(0x100), 0xcc);
+        assert_eq!(overlay.read_u16_le(0x0ff), 0xccbb);
+        // Disabled
+        ctrl.borrow_mut().write(0, 1);
+        assert!(!overlay.enabled());
+        assert_eq!(overlay.read(0x0ff), 0xcc);
+        assert_eq!(overlay.read_u16_le(0x0ff), 0xcccc);
+    }
+
+    #[test]
+    fn device_write_works() {
+        let (ctrl, mut overlay) = setup();
+        assert_eq!(
+            overlay.try_write(0x000, 0xaa),
+            Err(BusError::ReadOnly(0x000))
+        );
+        overlay.write(0x100, 0xaa);
+        ctrl.borrow_mut().write(0, 1);
+        overlay.write(0x000, 0xaa);
+        assert_eq!(overlay.read(0x000), 0xaa);
+        assert_eq!(overlay.read(0x100), 0xaa);
+    }
+
+    #[test]
+    fn when_works() {
+        let (ctrl, overlay) = setup();
+        let overlay = overlay.when(|ctrl| ctrl & 0x01 == 0);
+        ctrl.borrow_mut().write(0, 0x02);
+        assert!(overlay.enabled());
+        ctrl.borrow_mut().write(0, 0x03);
+        assert!(!overlay.enabled());
+    }
+
+    #[test]
+    fn block_reset_works() {
+        let (ctrl, mut overlay) = setup();
+        ctrl.borrow_mut().write(0, 1);
+        overlay.reset();
+        assert!(overlay.enabled());
+    }
+
+    #[test]
+    fn bus_mapped_works() {
+        // Let's emulate the Game Boy's boot ROM handoff
+        let (ctrl, overlay) = setup();
+        let mut bus = Bus::new();
+        bus.map(0x0000, overlay.to_shared());
+        bus.map(0xff50, ctrl);
+        assert_eq!(bus.read(0x0000), 0xbb);
+        // Disable the boot ROM
+        bus.write(0xff50, 0x01);
+        assert_eq!(bus.read(0x0000), 0xcc);
+        // Resetting the bus re-enables it
+        bus.reset();
+        assert_eq!(bus.read(0x0000), 0xbb);
+    }
+}
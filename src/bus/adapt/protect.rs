@@ -0,0 +1,348 @@
+use std::fmt::Debug;
+use std::ops::Range;
+use std::rc::Rc;
+
+use crate::blk::Block;
+use crate::bus::open::OPEN;
+use crate::bus::BusError;
+use crate::dev::{self, Device, SharedDevice};
+
+/// Protection policy.
+///
+/// Determines how a [`ReadOnly`] or [`WriteOnly`] adapter responds to a
+/// blocked access. Blocked reads which are not redirected yield `0xff`.
+#[derive(Clone, Default)]
+pub enum Protect {
+    /// Blocked accesses are silently ignored.
+    #[default]
+    Ignore,
+    /// Blocked accesses are ignored, after calling the function with their
+    /// address and, for writes, their value (reads pass `None`).
+    Log(Rc<dyn Fn(usize, Option<u8>)>),
+    /// Blocked accesses are an error.
+    ///
+    /// [`Device::read`] and [`Device::write`] panic, while their fallible
+    /// counterparts return [`BusError::WriteOnly`] or [`BusError::ReadOnly`]
+    /// respectively.
+    Error,
+    /// Blocked accesses are forwarded to another device.
+    Redirect(SharedDevice),
+}
+
+impl Debug for Protect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ignore => write!(f, "Ignore"),
+            Self::Log(_) => f.debug_tuple("Log").finish_non_exhaustive(),
+            Self::Error => write!(f, "Error"),
+            Self::Redirect(dev) => f.debug_tuple("Redirect").field(dev).finish(),
+        }
+    }
+}
+
+/// Read-only adapter.
+///
+/// # Usage
+///
+/// The `ReadOnly` adapter write-protects the underlying [`Device`], handling
+/// writes according to its [`Protect`] policy. This allows, for example,
+/// writes to cartridge ROM space to be ignored rather than panicking.
+///
+/// Pokes are not blocked, and are forwarded as-is.
+#[derive(Debug)]
+pub struct ReadOnly {
+    dev: SharedDevice,
+    policy: Protect,
+}
+
+impl ReadOnly {
+    /// Constructs a new `ReadOnly`.
+    pub fn new(dev: SharedDevice, policy: Protect) -> Self {
+        Self { dev, policy }
+    }
+}
+
+impl Block for ReadOnly {
+    fn reset(&mut self) {
+        self.dev.borrow_mut().reset();
+        if let Protect::Redirect(dev) = &self.policy {
+            dev.borrow_mut().reset();
+        }
+    }
+}
+
+impl Device for ReadOnly {
+    fn contains(&self, index: usize) -> bool {
+        self.dev.borrow().contains(index)
+    }
+
+    fn len(&self) -> usize {
+        self.dev.borrow().len()
+    }
+
+    fn extent(&self) -> Range<usize> {
+        self.dev.borrow().extent()
+    }
+
+    fn peek(&self, index: usize) -> u8 {
+        self.dev.borrow().peek(index)
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        self.try_write(index, value)
+            .unwrap_or_else(|err| panic!("`<ReadOnly as Device>::write()`: {err}"));
+    }
+
+    fn read(&mut self, index: usize) -> u8 {
+        self.dev.borrow_mut().read(index)
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        self.dev.borrow_mut().poke(index, value);
+    }
+
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        self.dev.borrow_mut().try_read(index)
+    }
+
+    fn try_write(&mut self, index: usize, value: u8) -> Result<(), BusError> {
+        match &self.policy {
+            Protect::Ignore => Ok(()),
+            Protect::Log(f) => {
+                f(index, Some(value));
+                Ok(())
+            }
+            Protect::Error => Err(BusError::ReadOnly(index)),
+            Protect::Redirect(dev) => dev.borrow_mut().try_write(index, value),
+        }
+    }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        self.dev.borrow_mut().read_bytes(index, buf);
+    }
+
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        match &self.policy {
+            Protect::Redirect(dev) => dev.borrow_mut().write_bytes(index, buf),
+            _ => {
+                for (offset, &byte) in buf.iter().enumerate() {
+                    self.write(index + offset, byte);
+                }
+            }
+        }
+    }
+
+    fn path(&self, index: usize) -> Vec<String> {
+        dev::resolve(&self.dev, None, index)
+    }
+}
+
+/// Write-only adapter.
+///
+/// # Usage
+///
+/// The `WriteOnly` adapter read-protects the underlying [`Device`], handling
+/// reads according to its [`Protect`] policy. This is useful for modelling
+/// write-only control registers.
+///
+/// Peeks are not blocked, and are forwarded as-is.
+#[derive(Debug)]
+pub struct WriteOnly {
+    dev: SharedDevice,
+    policy: Protect,
+}
+
+impl WriteOnly {
+    /// Constructs a new `WriteOnly`.
+    pub fn new(dev: SharedDevice, policy: Protect) -> Self {
+        Self { dev, policy }
+    }
+}
+
+impl Block for WriteOnly {
+    fn reset(&mut self) {
+        self.dev.borrow_mut().reset();
+        if let Protect::Redirect(dev) = &self.policy {
+            dev.borrow_mut().reset();
+        }
+    }
+}
+
+impl Device for WriteOnly {
+    fn contains(&self, index: usize) -> bool {
+        self.dev.borrow().contains(index)
+    }
+
+    fn len(&self) -> usize {
+        self.dev.borrow().len()
+    }
+
+    fn extent(&self) -> Range<usize> {
+        self.dev.borrow().extent()
+    }
+
+    fn peek(&self, index: usize) -> u8 {
+        self.dev.borrow().peek(index)
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        self.dev.borrow_mut().write(index, value);
+    }
+
+    fn read(&mut self, index: usize) -> u8 {
+        self.try_read(index)
+            .unwrap_or_else(|err| panic!("`<WriteOnly as Device>::read()`: {err}"))
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        self.dev.borrow_mut().poke(index, value);
+    }
+
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        match &self.policy {
+            Protect::Ignore => Ok(OPEN),
+            Protect::Log(f) => {
+                f(index, None);
+                Ok(OPEN)
+            }
+            Protect::Error => Err(BusError::WriteOnly(index)),
+            Protect::Redirect(dev) => dev.borrow_mut().try_read(index),
+        }
+    }
+
+    fn try_write(&mut self, index: usize, value: u8) -> Result<(), BusError> {
+        self.dev.borrow_mut().try_write(index, value)
+    }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        match &self.policy {
+            Protect::Redirect(dev) => dev.borrow_mut().read_bytes(index, buf),
+            _ => {
+                for (offset, byte) in buf.iter_mut().enumerate() {
+                    *byte = self.read(index + offset);
+                }
+            }
+        }
+    }
+
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        self.dev.borrow_mut().write_bytes(index, buf);
+    }
+
+    fn path(&self, index: usize) -> Vec<String> {
+        dev::resolve(&self.dev, None, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+    use crate::mem::{Ram, Rom};
+
+    fn setup(policy: Protect) -> (SharedDevice, ReadOnly) {
+        let rom = Rom::<0x100>::from(&[0x55; 0x100]).to_shared();
+        (rom.clone(), ReadOnly::new(rom, policy))
+    }
+
+    #[test]
+    fn new_works() {
+        let _ = setup(Protect::default());
+    }
+
+    #[test]
+    fn read_only_ignore_works() {
+        let (rom, mut ro) = setup(Protect::Ignore);
+        ro.write(0x00, 0xaa);
+        assert_eq!(ro.try_write(0x01, 0xaa), Ok(()));
+        ro.write_u16_le(0x02, 0xaaaa);
+        assert_eq!(ro.read_u32_le(0x00), 0x55555555);
+        assert_eq!(rom.borrow().peek(0x00), 0x55);
+    }
+
+    #[test]
+    fn read_only_log_works() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let (_, mut ro) = setup(Protect::Log(Rc::new({
+            let log = log.clone();
+            move |index, value| log.borrow_mut().push((index, value))
+        })));
+        ro.write(0x10, 0xaa);
+        ro.write_u16_be(0x20, 0x1234);
+        assert_eq!(
+            *log.borrow(),
+            [(0x10, Some(0xaa)), (0x20, Some(0x12)), (0x21, Some(0x34))]
+        );
+        assert_eq!(ro.read(0x10), 0x55);
+    }
+
+    #[test]
+    fn read_only_error_works() {
+        let (_, mut ro) = setup(Protect::Error);
+        assert_eq!(ro.try_write(0x10, 0xaa), Err(BusError::ReadOnly(0x10)));
+        assert_eq!(ro.try_read(0x10), Ok(0x55));
+    }
+
+    #[test]
+    #[should_panic]
+    fn read_only_error_panics() {
+        let (_, mut ro) = setup(Protect::Error);
+        ro.write(0x10, 0xaa);
+    }
+
+    #[test]
+    fn read_only_redirect_works() {
+        let ram = Ram::<0x100>::new().to_shared();
+        let (_, mut ro) = setup(Protect::Redirect(ram.clone()));
+        ro.write(0x10, 0xaa);
+        ro.write_u16_le(0x20, 0x1234);
+        assert_eq!(ro.read(0x10), 0x55);
+        assert_eq!(ram.borrow().peek(0x10), 0xaa);
+        assert_eq!(ram.borrow_mut().read_u16_le(0x20), 0x1234);
+    }
+
+    #[test]
+    fn read_only_poke_works() {
+        let (_, mut ro) = setup(Protect::Error);
+        ro.poke(0x10, 0xaa);
+        assert_eq!(ro.peek(0x10), 0xaa);
+    }
+
+    #[test]
+    fn write_only_works() {
+        let ram = Ram::<0x100>::from(&[0x55; 0x100]).to_shared();
+        // Test ignore
+        let mut wo = WriteOnly::new(ram.clone(), Protect::Ignore);
+        wo.write(0x10, 0xaa);
+        assert_eq!(wo.read(0x10), 0xff);
+        assert_eq!(wo.read_u16_le(0x10), 0xffff);
+        assert_eq!(wo.peek(0x10), 0xaa);
+        // Test error
+        let mut wo = WriteOnly::new(ram.clone(), Protect::Error);
+        assert_eq!(wo.try_read(0x10), Err(BusError::WriteOnly(0x10)));
+        assert_eq!(wo.try_write(0x10, 0xbb), Ok(()));
+        // Test redirect
+        let rom = Rom::<0x100>::from(&[0x66; 0x100]).to_shared();
+        let mut wo = WriteOnly::new(ram.clone(), Protect::Redirect(rom));
+        assert_eq!(wo.read(0x10), 0x66);
+        assert_eq!(wo.read_u16_le(0x10), 0x6666);
+        assert_eq!(ram.borrow().peek(0x10), 0xbb);
+    }
+
+    #[test]
+    fn write_only_log_works() {
+        let ram = Ram::<0x100>::from(&[0x55; 0x100]).to_shared();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut wo = WriteOnly::new(
+            ram,
+            Protect::Log(Rc::new({
+                let log = log.clone();
+                move |index, value| log.borrow_mut().push((index, value))
+            })),
+        );
+        wo.write(0x10, 0xff);
+        assert_eq!(wo.read(0x10), 0xff);
+        assert_eq!(*log.borrow(), [(0x10, None)]);
+    }
+}
@@ -1,12 +1,17 @@
+use std::ops::Range;
+
 use crate::blk::Block;
-use crate::dev::{Device, SharedDevice};
+use crate::bus::BusError;
+use crate::dev::{self, Device, SharedDevice};
 
 /// Address remap.
 ///
 /// # Usage
 ///
 /// The `Remap` adapter shifts the [`Device`]'s effective address space by the
-/// provided offset.
+/// provided offset, such that address `index` is forwarded to `index -
+/// offset`. Addresses shifted outside of the device's address space are out
+/// of bounds.
 ///
 /// In conjunction with [`View`](super::View), devices can be partially or
 /// completely mapped into another address space as desired.
@@ -21,6 +26,19 @@ impl Remap {
     pub fn new(offset: isize, dev: SharedDevice) -> Self {
         Self { offset, dev }
     }
+
+    /// Translates `index` into the underlying device.
+    ///
+    /// Returns `None` for addresses shifted outside of the device's address
+    /// space.
+    fn translate(&self, index: usize) -> Option<usize> {
+        let offset = self.offset.unsigned_abs();
+        if self.offset < 0 {
+            index.checked_add(offset)
+        } else {
+            index.checked_sub(offset)
+        }
+    }
 }
 
 impl Block for Remap {
@@ -31,27 +49,117 @@ impl Block for Remap {
 
 impl Device for Remap {
     fn contains(&self, index: usize) -> bool {
-        self.dev.borrow().contains(index)
+        match self.translate(index) {
+            Some(index) => self.dev.borrow().contains(index),
+            None => false,
+        }
     }
 
+    /// Addresses below a positive offset are included in the length, whereas
+    /// those shifted below zero by a negative offset are not.
     fn len(&self) -> usize {
-        self.dev.borrow().len()
+        let len = self.dev.borrow().len();
+        let offset = self.offset.unsigned_abs();
+        if self.offset < 0 {
+            len.saturating_sub(offset)
+        } else {
+            len.saturating_add(offset)
+        }
+    }
+
+    /// The underlying device's extent is shifted by the offset.
+    fn extent(&self) -> Range<usize> {
+        let Range { start, end } = self.dev.borrow().extent();
+        let offset = self.offset.unsigned_abs();
+        if self.offset < 0 {
+            start.saturating_sub(offset)..end.saturating_sub(offset)
+        } else {
+            start.saturating_add(offset)..end.saturating_add(offset)
+        }
     }
 
-    fn read(&self, index: usize) -> u8 {
-        let index = (index as isize - self.offset) as usize;
-        self.dev.borrow().read(index)
+    fn peek(&self, index: usize) -> u8 {
+        match self.translate(index) {
+            Some(index) => self.dev.borrow().peek(index),
+            None => panic!(
+                "`<Remap as Device>::peek()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
     }
 
     fn write(&mut self, index: usize, value: u8) {
-        let index = (index as isize - self.offset) as usize;
-        self.dev.borrow_mut().write(index, value);
+        match self.translate(index) {
+            Some(index) => self.dev.borrow_mut().write(index, value),
+            None => panic!(
+                "`<Remap as Device>::write()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn read(&mut self, index: usize) -> u8 {
+        match self.translate(index) {
+            Some(index) => self.dev.borrow_mut().read(index),
+            None => panic!(
+                "`<Remap as Device>::read()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        match self.translate(index) {
+            Some(index) => self.dev.borrow_mut().poke(index, value),
+            None => panic!(
+                "`<Remap as Device>::poke()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        let index = self.translate(index).ok_or(BusError::OutOfBounds(index))?;
+        self.dev.borrow_mut().try_read(index)
+    }
+
+    fn try_write(&mut self, index: usize, value: u8) -> Result<(), BusError> {
+        let index = self.translate(index).ok_or(BusError::OutOfBounds(index))?;
+        self.dev.borrow_mut().try_write(index, value)
+    }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        match self.translate(index) {
+            Some(index) => self.dev.borrow_mut().read_bytes(index, buf),
+            None => panic!(
+                "`<Remap as Device>::read_bytes()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        match self.translate(index) {
+            Some(index) => self.dev.borrow_mut().write_bytes(index, buf),
+            None => panic!(
+                "`<Remap as Device>::write_bytes()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn path(&self, index: usize) -> Vec<String> {
+        match self.translate(index) {
+            Some(index) => dev::resolve(&self.dev, None, index),
+            None => Vec::new(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bus::Bus;
     use crate::mem::Ram;
 
     #[test]
@@ -63,34 +171,47 @@ mod tests {
     #[test]
     fn device_contains_works() {
         let ram = Ram::<0x100>::new().to_shared();
-        let remap = Remap::new(0x080, ram);
-        (0x00..=0x7f).for_each(|addr| assert!(remap.contains(addr)));
-        (0x80..=0xff).for_each(|addr| assert!(remap.contains(addr)));
+        let remap = Remap::new(0x080, ram.clone());
+        (0x000..0x080).for_each(|addr| assert!(!remap.contains(addr)));
+        (0x080..0x180).for_each(|addr| assert!(remap.contains(addr)));
+        assert!(!remap.contains(0x180));
+        let remap = Remap::new(-0x080, ram);
+        (0x000..0x080).for_each(|addr| assert!(remap.contains(addr)));
+        assert!(!remap.contains(0x080));
+        assert!(!remap.contains(usize::MAX));
     }
 
     #[test]
     fn device_len_works() {
         assert_eq!(Remap::new(0x0, Ram::<0x0>::new().to_shared()).len(), 0);
-        assert_eq!(Remap::new(0x8, Ram::<0x1>::new().to_shared()).len(), 0x1);
-        assert_eq!(Remap::new(0x80, Ram::<0x10>::new().to_shared()).len(), 0x10);
+        assert_eq!(Remap::new(0x8, Ram::<0x1>::new().to_shared()).len(), 0x9);
+        assert_eq!(Remap::new(0x80, Ram::<0x10>::new().to_shared()).len(), 0x90);
         assert_eq!(
             Remap::new(0x800, Ram::<0x100>::new().to_shared()).len(),
-            0x100
+            0x900
+        );
+        assert_eq!(
+            Remap::new(-0x800, Ram::<0x1000>::new().to_shared()).len(),
+            0x800
+        );
+        assert_eq!(
+            Remap::new(-0x80000, Ram::<0x10000>::new().to_shared()).len(),
+            0
         );
         assert_eq!(
-            Remap::new(0x8000, Ram::<0x1000>::new().to_shared()).len(),
-            0x1000
+            Remap::new(isize::MAX, Ram::<0x10000>::new().to_shared()).len(),
+            isize::MAX as usize + 0x10000
         );
         assert_eq!(
-            Remap::new(0x80000, Ram::<0x10000>::new().to_shared()).len(),
-            0x10000
+            Remap::new(isize::MIN, Ram::<0x10000>::new().to_shared()).len(),
+            0
         );
     }
 
     #[test]
     fn device_read_works() {
         let ram = Ram::<0x100>::from(&[0xaa; 0x100]).to_shared();
-        let remap = Remap::new(0x080, ram);
+        let mut remap = Remap::new(0x080, ram);
         (0x080..0x180).for_each(|addr| {
             assert_eq!(remap.read(addr), 0xaa);
         });
@@ -104,10 +225,101 @@ mod tests {
             remap.write(addr, 0xaa);
         });
         (0x000..0x080).for_each(|addr| {
-            assert_eq!(ram.borrow().read(addr), 0xaa);
+            assert_eq!(ram.borrow_mut().read(addr), 0xaa);
         });
         (0x080..0x100).for_each(|addr| {
-            assert_eq!(ram.borrow().read(addr), 0x00);
+            assert_eq!(ram.borrow_mut().read(addr), 0x00);
         });
     }
+
+    #[test]
+    fn device_try_read_works() {
+        let ram = Ram::<0x100>::from(&[0xaa; 0x100]).to_shared();
+        let mut remap = Remap::new(0x080, ram);
+        (0x080..0x180).for_each(|addr| assert_eq!(remap.try_read(addr), Ok(0xaa)));
+        assert_eq!(remap.try_read(0x180), Err(BusError::OutOfBounds(0x100)));
+    }
+
+    #[test]
+    fn device_try_write_works() {
+        let ram = Ram::<0x100>::new().to_shared();
+        let mut remap = Remap::new(0x080, ram.clone());
+        (0x080..0x180).for_each(|addr| assert_eq!(remap.try_write(addr, 0xaa), Ok(())));
+        assert_eq!(
+            remap.try_write(0x180, 0xaa),
+            Err(BusError::OutOfBounds(0x100))
+        );
+        (0x000..0x100).for_each(|addr| assert_eq!(ram.borrow_mut().read(addr), 0xaa));
+    }
+
+    #[test]
+    fn device_read_write_word_works() {
+        let ram = Ram::<0x100>::new().to_shared();
+        let mut remap = Remap::new(0x080, ram.clone());
+        remap.write_u16_le(0x080, 0x0123);
+        assert_eq!(ram.borrow_mut().read_u16_be(0x000), 0x2301);
+        assert_eq!(remap.read_u16_le(0x080), 0x0123);
+    }
+
+    #[test]
+    fn device_peek_poke_works() {
+        let ram = Ram::<0x100>::new().to_shared();
+        let mut remap = Remap::new(0x080, ram.clone());
+        (0x080..0x180).for_each(|addr| remap.poke(addr, 0xaa));
+        (0x080..0x180).for_each(|addr| assert_eq!(remap.peek(addr), 0xaa));
+        (0x000..0x100).for_each(|addr| assert_eq!(ram.borrow_mut().read(addr), 0xaa));
+    }
+
+    #[test]
+    fn device_negative_offset_works() {
+        let ram = Ram::<0x100>::new().to_shared();
+        let mut remap = Remap::new(-0x080, ram.clone());
+        remap.write(0x000, 0xaa);
+        assert_eq!(ram.borrow().peek(0x080), 0xaa);
+        assert_eq!(remap.read(0x000), 0xaa);
+        assert_eq!(remap.try_read(0x080), Err(BusError::OutOfBounds(0x100)));
+        // Large offsets must not overflow
+        let mut remap = Remap::new(isize::MIN, ram);
+        assert_eq!(
+            remap.try_read(usize::MAX),
+            Err(BusError::OutOfBounds(usize::MAX))
+        );
+    }
+
+    #[test]
+    fn device_positive_offset_works() {
+        let ram = Ram::<0x100>::new().to_shared();
+        let mut remap = Remap::new(isize::MAX, ram);
+        assert_eq!(remap.try_read(0x0), Err(BusError::OutOfBounds(0x0)));
+        assert_eq!(remap.try_read(isize::MAX as usize), Ok(0x00));
+    }
+
+    #[test]
+    fn bus_mapped_works() {
+        let mut bus = Bus::new();
+        let ram = Ram::<0x100>::new().to_shared();
+        // Shift the RAM up within its mapping
+        bus.map(0x1000, Remap::new(0x080, ram.clone()).to_shared());
+        assert_eq!(bus.len(), 0x180);
+        assert!(!bus.contains(0x1000));
+        assert!(bus.contains(0x1080));
+        assert!(bus.contains(0x117f));
+        assert!(!bus.contains(0x1180));
+        bus.write(0x1080, 0xaa);
+        assert_eq!(ram.borrow().peek(0x000), 0xaa);
+        assert_eq!(bus.try_read(0x1000), Err(BusError::Unmapped(0x1000)));
+        // Shift the RAM down within its mapping
+        let mut bus = Bus::new();
+        bus.map(0x1000, Remap::new(-0x080, ram.clone()).to_shared());
+        bus.map(0x1080, Ram::<0x80>::from(&[0xbb; 0x80]).to_shared());
+        assert_eq!(bus.len(), 0x100);
+        assert_eq!(bus.read(0x1000), 0x00);
+        assert_eq!(bus.read(0x1080), 0xbb);
+        bus.write(0x107f, 0xcc);
+        assert_eq!(ram.borrow().peek(0x0ff), 0xcc);
+        // Check the page table agrees
+        bus.set_page_size(Some(0x40));
+        assert_eq!(bus.read(0x107f), 0xcc);
+        assert_eq!(bus.read(0x1080), 0xbb);
+    }
 }
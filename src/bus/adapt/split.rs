@@ -0,0 +1,212 @@
+use std::ops::Range;
+use std::rc::Rc;
+
+use crate::blk::Block;
+use crate::bus::BusError;
+use crate::dev::{self, Device, SharedDevice};
+
+/// Read/write split.
+///
+/// # Usage
+///
+/// The `Split` adapter routes reads and writes to separate devices sharing
+/// the same address space. This allows, for example, an MBC-style mapper to
+/// be composed from a [`Rom`](crate::mem::Rom) which is read from, overlaid by
+/// the control [`Register`](crate::reg::Register)s it writes to.
+///
+/// Peeks are routed alongside reads, and pokes alongside writes. Writes (and
+/// pokes) to addresses covered only by the read device are ignored, or, when
+/// fallible, return [`BusError::ReadOnly`].
+#[derive(Debug)]
+pub struct Split {
+    read: SharedDevice,
+    write: SharedDevice,
+}
+
+impl Split {
+    /// Constructs a new `Split`.
+    pub fn new(read: SharedDevice, write: SharedDevice) -> Self {
+        Self { read, write }
+    }
+}
+
+impl Block for Split {
+    fn reset(&mut self) {
+        self.read.borrow_mut().reset();
+        // Avoid resetting a device twice
+        if !Rc::ptr_eq(&self.read, &self.write) {
+            self.write.borrow_mut().reset();
+        }
+    }
+}
+
+impl Device for Split {
+    fn contains(&self, index: usize) -> bool {
+        self.read.borrow().contains(index) || self.write.borrow().contains(index)
+    }
+
+    fn len(&self) -> usize {
+        self.read.borrow().len().max(self.write.borrow().len())
+    }
+
+    fn extent(&self) -> Range<usize> {
+        dev::union(self.read.borrow().extent(), self.write.borrow().extent())
+    }
+
+    fn peek(&self, index: usize) -> u8 {
+        self.read.borrow().peek(index)
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        let mut dev = self.write.borrow_mut();
+        if dev.contains(index) {
+            dev.write(index, value);
+        }
+    }
+
+    fn read(&mut self, index: usize) -> u8 {
+        self.read.borrow_mut().read(index)
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        let mut dev = self.write.borrow_mut();
+        if dev.contains(index) {
+            dev.poke(index, value);
+        }
+    }
+
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        self.read.borrow_mut().try_read(index)
+    }
+
+    fn try_write(&mut self, index: usize, value: u8) -> Result<(), BusError> {
+        let mut dev = self.write.borrow_mut();
+        if !dev.contains(index) {
+            return Err(BusError::ReadOnly(index));
+        }
+        dev.try_write(index, value)
+    }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        self.read.borrow_mut().read_bytes(index, buf);
+    }
+
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        let mut dev = self.write.borrow_mut();
+        // Only forward in bulk if every write lands on the write device
+        let end = index + buf.len();
+        if buf.is_empty() || (dev.contains(index) && dev.contains(end - 1)) {
+            dev.write_bytes(index, buf);
+        } else {
+            drop(dev);
+            for (index, &value) in (index..).zip(buf) {
+                self.write(index, value);
+            }
+        }
+    }
+
+    fn path(&self, index: usize) -> Vec<String> {
+        if self.read.borrow().contains(index) {
+            dev::resolve(&self.read, None, index)
+        } else {
+            dev::resolve(&self.write, None, index)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Bus;
+    use crate::mem::{Ram, Rom};
+    use crate::reg::Register;
+
+    fn setup() -> (SharedDevice, Split) {
+        let rom = Rom::<0x100>::from(&[0x55; 0x100]).to_shared();
+        let ram = Ram::<0x80>::new().to_shared();
+        (ram.clone(), Split::new(rom, ram))
+    }
+
+    #[test]
+    fn new_works() {
+        let _ = setup();
+    }
+
+    #[test]
+    fn device_contains_works() {
+        let (_, split) = setup();
+        (0x00..0x100).for_each(|addr| assert!(split.contains(addr)));
+        assert!(!split.contains(0x100));
+    }
+
+    #[test]
+    fn device_len_works() {
+        let (_, split) = setup();
+        assert_eq!(split.len(), 0x100);
+    }
+
+    #[test]
+    fn device_read_write_works() {
+        let (ram, mut split) = setup();
+        split.write(0x10, 0xaa);
+        assert_eq!(split.read(0x10), 0x55);
+        assert_eq!(ram.borrow().peek(0x10), 0xaa);
+        // Check fallible accesses
+        assert_eq!(split.try_write(0x80, 0xaa), Err(BusError::ReadOnly(0x80)));
+        assert_eq!(split.try_read(0x80), Ok(0x55));
+        // Check wide accesses
+        split.write_u16_le(0x20, 0x1234);
+        assert_eq!(split.read_u16_le(0x20), 0x5555);
+        assert_eq!(ram.borrow_mut().read_u16_le(0x20), 0x1234);
+    }
+
+    #[test]
+    fn device_write_read_only_works() {
+        let (ram, mut split) = setup();
+        // Writes past the RAM land only on the ROM, so are ignored
+        split.write(0x80, 0xaa);
+        split.poke(0x81, 0xaa);
+        split.write_bytes(0x7e, &[0x11, 0x22, 0x33, 0x44]);
+        assert_eq!(ram.borrow().peek(0x7e), 0x11);
+        assert_eq!(ram.borrow().peek(0x7f), 0x22);
+        assert_eq!(split.peek(0x80), 0x55);
+        assert_eq!(split.peek(0x81), 0x55);
+    }
+
+    #[test]
+    fn bus_write_read_only_errs() {
+        let (_, split) = setup();
+        let mut bus = Bus::new();
+        bus.map(0x4000, split.to_shared());
+        assert_eq!(bus.try_write(0x4080, 0xaa), Err(BusError::ReadOnly(0x4080)));
+        assert_eq!(bus.read(0x4080), 0x55);
+    }
+
+    #[test]
+    fn device_peek_poke_works() {
+        let (ram, mut split) = setup();
+        split.poke(0x10, 0xaa);
+        assert_eq!(split.peek(0x10), 0x55);
+        assert_eq!(ram.borrow().peek(0x10), 0xaa);
+    }
+
+    #[test]
+    fn block_reset_works() {
+        let (ram, mut split) = setup();
+        split.write(0x10, 0xaa);
+        split.reset();
+        assert_eq!(ram.borrow().peek(0x10), 0x00);
+    }
+
+    #[test]
+    fn bus_mapped_works() {
+        // Compose a mapper from a ROM and its bank select register
+        let rom = Rom::<0x4000>::from(&[0x55; 0x4000]).to_shared();
+        let reg = Register::<u8>::new().to_shared();
+        let mut bus = Bus::new();
+        bus.map(0x0000, Split::new(rom, reg.clone()).to_shared());
+        bus.write(0x0000, 0x01);
+        assert_eq!(bus.read(0x0000), 0x55);
+        assert_eq!(reg.borrow().peek(0x0), 0x01);
+    }
+}
@@ -0,0 +1,322 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::ops::Range;
+
+use crate::blk::Block;
+use crate::bus::{Access, BusError};
+use crate::dev::{self, Device, SharedDevice};
+
+/// Access tracer.
+///
+/// # Usage
+///
+/// The `Trace` adapter records every read and write made to the underlying
+/// [`Device`] as a [`Record`], either into a bounded ring buffer or by passing
+/// it to a user-supplied sink. Peeks and pokes are not recorded.
+///
+/// Recorded addresses are those seen by the adapter. To trace an entire
+/// address space, wrap the outermost [`Bus`](crate::bus::Bus).
+pub struct Trace {
+    dev: SharedDevice,
+    sink: Sink,
+    range: Option<Range<usize>>,
+    clock: Option<Box<dyn Fn() -> u64>>,
+}
+
+impl Trace {
+    /// Constructs a new `Trace` which keeps the last `capacity` records.
+    pub fn new(dev: SharedDevice, capacity: usize) -> Self {
+        Self::with_sink(
+            dev,
+            Sink::Buffer(VecDeque::with_capacity(capacity), capacity),
+        )
+    }
+
+    /// Constructs a new `Trace` which passes each record to the provided
+    /// sink.
+    pub fn with(dev: SharedDevice, sink: impl FnMut(&Record) + 'static) -> Self {
+        Self::with_sink(dev, Sink::Call(Box::new(sink)))
+    }
+
+    fn with_sink(dev: SharedDevice, sink: Sink) -> Self {
+        Self {
+            dev,
+            sink,
+            range: None,
+            clock: None,
+        }
+    }
+
+    /// Restricts tracing to accesses within the provided address range.
+    #[must_use]
+    pub fn filter(mut self, range: Range<usize>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Timestamps each record with the cycle count yielded by the provided
+    /// function.
+    #[must_use]
+    pub fn clock(mut self, clock: impl Fn() -> u64 + 'static) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+
+    /// Returns an iterator over the buffered records, oldest first.
+    ///
+    /// Yields nothing when records are passed to a sink.
+    pub fn records(&self) -> impl Iterator<Item = &Record> {
+        match &self.sink {
+            Sink::Buffer(buf, _) => Some(buf.iter()),
+            Sink::Call(_) => None,
+        }
+        .into_iter()
+        .flatten()
+    }
+
+    /// Takes the buffered records, clearing them.
+    pub fn take(&mut self) -> Vec<Record> {
+        match &mut self.sink {
+            Sink::Buffer(buf, _) => buf.drain(..).collect(),
+            Sink::Call(_) => Vec::new(),
+        }
+    }
+
+    /// Records an access, unless filtered out.
+    fn record(&mut self, index: usize, access: Access, value: u8) {
+        if let Some(range) = &self.range {
+            if !range.contains(&index) {
+                return;
+            }
+        }
+        let record = Record {
+            index,
+            access,
+            value,
+            cycle: self.clock.as_ref().map(|clock| clock()),
+        };
+        match &mut self.sink {
+            Sink::Buffer(_, 0) => (),
+            Sink::Buffer(buf, capacity) => {
+                if buf.len() == *capacity {
+                    buf.pop_front();
+                }
+                buf.push_back(record);
+            }
+            Sink::Call(sink) => sink(&record),
+        }
+    }
+}
+
+impl Debug for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Trace")
+            .field("dev", &self.dev)
+            .field("sink", &self.sink)
+            .field("range", &self.range)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Block for Trace {
+    fn reset(&mut self) {
+        self.dev.borrow_mut().reset();
+    }
+}
+
+impl Device for Trace {
+    fn contains(&self, index: usize) -> bool {
+        self.dev.borrow().contains(index)
+    }
+
+    fn len(&self) -> usize {
+        self.dev.borrow().len()
+    }
+
+    fn extent(&self) -> Range<usize> {
+        self.dev.borrow().extent()
+    }
+
+    fn peek(&self, index: usize) -> u8 {
+        self.dev.borrow().peek(index)
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        self.dev.borrow_mut().write(index, value);
+        self.record(index, Access::Write, value);
+    }
+
+    fn read(&mut self, index: usize) -> u8 {
+        let value = self.dev.borrow_mut().read(index);
+        self.record(index, Access::Read, value);
+        value
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        self.dev.borrow_mut().poke(index, value);
+    }
+
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        let value = self.dev.borrow_mut().try_read(index)?;
+        self.record(index, Access::Read, value);
+        Ok(value)
+    }
+
+    fn try_write(&mut self, index: usize, value: u8) -> Result<(), BusError> {
+        self.dev.borrow_mut().try_write(index, value)?;
+        self.record(index, Access::Write, value);
+        Ok(())
+    }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        self.dev.borrow_mut().read_bytes(index, buf);
+        for (offset, &value) in buf.iter().enumerate() {
+            self.record(index + offset, Access::Read, value);
+        }
+    }
+
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        self.dev.borrow_mut().write_bytes(index, buf);
+        for (offset, &value) in buf.iter().enumerate() {
+            self.record(index + offset, Access::Write, value);
+        }
+    }
+
+    fn path(&self, index: usize) -> Vec<String> {
+        dev::resolve(&self.dev, None, index)
+    }
+}
+
+/// Traced access.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Record {
+    /// Address of the access.
+    pub index: usize,
+    /// Kind of access.
+    pub access: Access,
+    /// Value read or written.
+    pub value: u8,
+    /// Cycle count at the time of the access, if a clock was provided.
+    pub cycle: Option<u64>,
+}
+
+/// Destination of traced accesses.
+enum Sink {
+    Buffer(VecDeque<Record>, usize),
+    Call(Box<dyn FnMut(&Record)>),
+}
+
+impl Debug for Sink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Buffer(buf, capacity) => {
+                f.debug_tuple("Buffer").field(buf).field(capacity).finish()
+            }
+            Self::Call(_) => f.debug_tuple("Call").finish_non_exhaustive(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::mem::Ram;
+
+    fn setup() -> Trace {
+        let ram = Ram::<0x100>::from(&[0x55; 0x100]).to_shared();
+        Trace::new(ram, 4)
+    }
+
+    #[test]
+    fn new_works() {
+        let _ = setup();
+    }
+
+    #[test]
+    fn records_works() {
+        let mut trace = setup();
+        trace.write(0x00, 0xaa);
+        assert_eq!(trace.read(0x00), 0xaa);
+        assert_eq!(trace.peek(0x01), 0x55);
+        trace.poke(0x01, 0xbb);
+        assert_eq!(
+            trace.take(),
+            [
+                Record {
+                    index: 0x00,
+                    access: Access::Write,
+                    value: 0xaa,
+                    cycle: None,
+                },
+                Record {
+                    index: 0x00,
+                    access: Access::Read,
+                    value: 0xaa,
+                    cycle: None,
+                },
+            ]
+        );
+        assert_eq!(trace.records().count(), 0);
+    }
+
+    #[test]
+    fn ring_buffer_works() {
+        let mut trace = setup();
+        (0x00..0x10).for_each(|addr| trace.write(addr, addr as u8));
+        assert_eq!(
+            trace.records().map(|rec| rec.index).collect::<Vec<_>>(),
+            [0x0c, 0x0d, 0x0e, 0x0f]
+        );
+    }
+
+    #[test]
+    fn filter_works() {
+        let mut trace = setup().filter(0x10..0x20);
+        trace.read(0x0f);
+        trace.read(0x10);
+        trace.read_u16_le(0x1f);
+        assert_eq!(
+            trace.records().map(|rec| rec.index).collect::<Vec<_>>(),
+            [0x10, 0x1f]
+        );
+    }
+
+    #[test]
+    fn clock_works() {
+        let cycle = Rc::new(Cell::new(0));
+        let mut trace = setup().clock({
+            let cycle = cycle.clone();
+            move || cycle.get()
+        });
+        trace.read(0x00);
+        cycle.set(4);
+        trace.read(0x00);
+        assert_eq!(
+            trace.records().map(|rec| rec.cycle).collect::<Vec<_>>(),
+            [Some(0), Some(4)]
+        );
+    }
+
+    #[test]
+    fn sink_works() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let ram = Ram::<0x100>::new().to_shared();
+        let mut trace = Trace::with(ram, {
+            let log = log.clone();
+            move |rec| log.borrow_mut().push(*rec)
+        });
+        trace.write_bytes(0x00, &[0x12, 0x34]);
+        assert_eq!(trace.try_read(0x100), Err(BusError::OutOfBounds(0x100)));
+        assert_eq!(
+            log.borrow()
+                .iter()
+                .map(|rec| (rec.index, rec.value))
+                .collect::<Vec<_>>(),
+            [(0x00, 0x12), (0x01, 0x34)]
+        );
+        assert_eq!(trace.records().count(), 0);
+    }
+}
@@ -2,7 +2,8 @@ use std::fmt::Debug;
 use std::ops::{Bound, RangeBounds};
 
 use crate::blk::Block;
-use crate::dev::{Device, SharedDevice};
+use crate::bus::BusError;
+use crate::dev::{self, Device, SharedDevice};
 
 /// Partial address view.
 ///
@@ -39,6 +40,14 @@ where
                 Bound::Unbounded => 0,
             }
     }
+
+    /// Translates `index`, provided all of `index..index + len` lies within
+    /// the view.
+    fn span(&self, index: usize, len: usize) -> Option<usize> {
+        let addr = self.translate(index);
+        let last = addr + len.saturating_sub(1);
+        (self.range.contains(&addr) && self.range.contains(&last)).then_some(addr)
+    }
 }
 
 impl<R> Block for View<R>
@@ -73,21 +82,78 @@ where
         end.saturating_sub(start)
     }
 
-    fn read(&self, index: usize) -> u8 {
-        let index = self.translate(index);
-        if self.range.contains(&index) {
-            self.dev.borrow().read(index)
-        } else {
-            panic!("`<View as Device>::read()`: index out of bounds");
+    fn peek(&self, index: usize) -> u8 {
+        match self.span(index, 1) {
+            Some(addr) => self.dev.borrow().peek(addr),
+            None => panic!(
+                "`<View as Device>::peek()`: {}",
+                BusError::OutOfBounds(index)
+            ),
         }
     }
 
     fn write(&mut self, index: usize, value: u8) {
-        let index = self.translate(index);
-        if self.range.contains(&index) {
-            self.dev.borrow_mut().write(index, value);
+        self.try_write(index, value)
+            .unwrap_or_else(|err| panic!("`<View as Device>::write()`: {err}"));
+    }
+
+    fn read(&mut self, index: usize) -> u8 {
+        self.try_read(index)
+            .unwrap_or_else(|err| panic!("`<View as Device>::read()`: {err}"))
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        match self.span(index, 1) {
+            Some(addr) => self.dev.borrow_mut().poke(addr, value),
+            None => panic!(
+                "`<View as Device>::poke()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        let addr = self.translate(index);
+        if self.range.contains(&addr) {
+            self.dev.borrow_mut().try_read(addr)
         } else {
-            panic!("`<View as Device>::write()`: index out of bounds");
+            Err(BusError::OutOfBounds(index))
+        }
+    }
+
+    fn try_write(&mut self, index: usize, value: u8) -> Result<(), BusError> {
+        let addr = self.translate(index);
+        if self.range.contains(&addr) {
+            self.dev.borrow_mut().try_write(addr, value)
+        } else {
+            Err(BusError::OutOfBounds(index))
+        }
+    }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        match self.span(index, buf.len()) {
+            Some(addr) => self.dev.borrow_mut().read_bytes(addr, buf),
+            None => panic!(
+                "`<View as Device>::read_bytes()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        match self.span(index, buf.len()) {
+            Some(addr) => self.dev.borrow_mut().write_bytes(addr, buf),
+            None => panic!(
+                "`<View as Device>::write_bytes()`: {}",
+                BusError::OutOfBounds(index)
+            ),
+        }
+    }
+
+    fn path(&self, index: usize) -> Vec<String> {
+        match self.span(index, 1) {
+            Some(addr) => dev::resolve(&self.dev, None, addr),
+            None => Vec::new(),
         }
     }
 }
@@ -132,12 +198,47 @@ mod tests {
     #[test]
     fn device_read_works() {
         let ram = Ram::<0x100>::from(&[0xaa; 0x100]).to_shared();
-        let view = View::new(ram, 0x40..0xc0);
+        let mut view = View::new(ram, 0x40..0xc0);
         (0..0x80).for_each(|addr| {
             assert_eq!(view.read(addr), 0xaa);
         });
     }
 
+    #[test]
+    fn device_try_read_works() {
+        let ram = Ram::<0x100>::from(&[0xaa; 0x100]).to_shared();
+        let mut view = View::new(ram, 0x40..0xc0);
+        (0..0x80).for_each(|addr| assert_eq!(view.try_read(addr), Ok(0xaa)));
+        assert_eq!(view.try_read(0x80), Err(BusError::OutOfBounds(0x80)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn device_read_out_of_bounds_panics() {
+        let ram = Ram::<0x100>::new().to_shared();
+        let mut view = View::new(ram, 0x40..0xc0);
+        view.read(0x80);
+    }
+
+    #[test]
+    fn device_read_write_word_works() {
+        let ram = Ram::<0x100>::new().to_shared();
+        let mut view = View::new(ram.clone(), 0x40..0xc0);
+        view.write_u16_be(0x00, 0x0123);
+        assert_eq!(ram.borrow_mut().read_u16_le(0x40), 0x2301);
+        assert_eq!(view.read_u16_le(0x00), 0x2301);
+        view.write_u32_le(0x7c, 0x01234567);
+        assert_eq!(view.read_u32_be(0x7c), 0x67452301);
+    }
+
+    #[test]
+    #[should_panic]
+    fn device_read_word_out_of_bounds_panics() {
+        let ram = Ram::<0x100>::new().to_shared();
+        let mut view = View::new(ram, 0x40..0xc0);
+        view.read_u16_le(0x7f);
+    }
+
     #[test]
     fn device_write_works() {
         let ram = Ram::<0x100>::new().to_shared();
@@ -146,13 +247,31 @@ mod tests {
             view.write(addr, 0xaa);
         });
         (0x000..0x040).for_each(|addr| {
-            assert_eq!(ram.borrow().read(addr), 0x00);
+            assert_eq!(ram.borrow_mut().read(addr), 0x00);
         });
         (0x040..0x0c0).for_each(|addr| {
-            assert_eq!(ram.borrow().read(addr), 0xaa);
+            assert_eq!(ram.borrow_mut().read(addr), 0xaa);
         });
         (0x0c0..0x100).for_each(|addr| {
-            assert_eq!(ram.borrow().read(addr), 0x00);
+            assert_eq!(ram.borrow_mut().read(addr), 0x00);
         });
     }
+
+    #[test]
+    fn device_try_write_works() {
+        let ram = Ram::<0x100>::new().to_shared();
+        let mut view = View::new(ram.clone(), 0x40..0xc0);
+        (0x00..0x80).for_each(|addr| assert_eq!(view.try_write(addr, 0xaa), Ok(())));
+        assert_eq!(view.try_write(0x80, 0xaa), Err(BusError::OutOfBounds(0x80)));
+        (0x40..0xc0).for_each(|addr| assert_eq!(ram.borrow_mut().read(addr), 0xaa));
+    }
+
+    #[test]
+    fn device_peek_poke_works() {
+        let ram = Ram::<0x100>::new().to_shared();
+        let mut view = View::new(ram.clone(), 0x40..0xc0);
+        (0x00..0x80).for_each(|addr| view.poke(addr, 0xaa));
+        (0x00..0x80).for_each(|addr| assert_eq!(view.peek(addr), 0xaa));
+        (0x40..0xc0).for_each(|addr| assert_eq!(ram.borrow_mut().read(addr), 0xaa));
+    }
 }
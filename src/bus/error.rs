@@ -0,0 +1,66 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Bus access error.
+///
+/// Returned by the fallible [`Device`](crate::dev::Device) accessors
+/// ([`try_read`](crate::dev::Device::try_read) and
+/// [`try_write`](crate::dev::Device::try_write)) when an access cannot be
+/// completed.
+///
+/// Each variant holds the offending address, relative to the device on which
+/// the access was attempted. A [`Bus`](crate::bus::Bus) rebases errors raised
+/// by its mapped devices into its own address space.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BusError {
+    /// No device is mapped at the address.
+    Unmapped(usize),
+    /// The address lies outside of the device's address space.
+    OutOfBounds(usize),
+    /// The address refers to read-only memory.
+    ReadOnly(usize),
+    /// The address refers to write-only memory.
+    WriteOnly(usize),
+    /// The address is not suitably aligned for the access.
+    Misaligned(usize),
+}
+
+impl BusError {
+    /// Returns the address at which the error occurred.
+    #[must_use]
+    pub fn addr(&self) -> usize {
+        match *self {
+            Self::Unmapped(addr)
+            | Self::OutOfBounds(addr)
+            | Self::ReadOnly(addr)
+            | Self::WriteOnly(addr)
+            | Self::Misaligned(addr) => addr,
+        }
+    }
+
+    /// Returns the same error, occurring at `addr` instead.
+    #[must_use]
+    pub fn with_addr(self, addr: usize) -> Self {
+        match self {
+            Self::Unmapped(_) => Self::Unmapped(addr),
+            Self::OutOfBounds(_) => Self::OutOfBounds(addr),
+            Self::ReadOnly(_) => Self::ReadOnly(addr),
+            Self::WriteOnly(_) => Self::WriteOnly(addr),
+            Self::Misaligned(_) => Self::Misaligned(addr),
+        }
+    }
+}
+
+impl Display for BusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unmapped(addr) => write!(f, "unmapped address: {addr:#x}"),
+            Self::OutOfBounds(addr) => write!(f, "address out of bounds: {addr:#x}"),
+            Self::ReadOnly(addr) => write!(f, "read-only address: {addr:#x}"),
+            Self::WriteOnly(addr) => write!(f, "write-only address: {addr:#x}"),
+            Self::Misaligned(addr) => write!(f, "misaligned address: {addr:#x}"),
+        }
+    }
+}
+
+impl Error for BusError {}
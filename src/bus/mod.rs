@@ -9,23 +9,62 @@
 //! Together with the [adapters](self::adapt), `Bus` is the primary method of emulating
 //! [memory-mapped I/O].
 //!
+//! Accesses made through a `Bus` can be observed with [watchpoints](Watchpoint),
+//! installed with [`Bus::watch`].
+//!
+//! The layout of a `Bus` can be inspected through [`Bus::regions`] and
+//! [`Bus::overlaps`], or printed as a memory map table through its [`Display`]
+//! implementation.
+//!
 //! [memory-mapped I/O]: https://en.wikipedia.org/wiki/Memory-mapped_I/O
 
 use std::collections::BTreeMap;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display, Write};
+use std::ops::Range;
 use std::rc::Rc;
 
 use crate::blk::Block;
-use crate::dev::{Device, SharedDevice};
+use crate::dev::{self, Device, SharedDevice};
 
 pub mod adapt;
+mod error;
+mod open;
+mod page;
+mod watch;
+
+pub use self::error::BusError;
+pub use self::open::OpenBus;
+pub use self::watch::{Access, Hit, Trigger, Watchpoint};
+
+/// Run of consecutive addresses, decoded to the same mapped device (if any).
+type Run = (Range<usize>, Option<(usize, SharedDevice)>);
 
 /// Address [bus][bus].
 ///
+/// Accesses to addresses where no device is mapped are handled according to
+/// the bus's [`OpenBus`] policy.
+///
+/// # Performance
+///
+/// By default, decoding an address walks the mapped devices in priority
+/// order. For hot buses, a page-table decoder can be enabled with
+/// [`Bus::set_page_size`], resolving most addresses in constant time. The
+/// table is rebuilt whenever the bus is remapped or reset, and assumes each
+/// device lies within its [`Device::extent`], offset by its base. Extents
+/// must therefore remain valid across any changes the guest can make (e.g. a
+/// [`Bank`](adapt::Bank) spans all of its banks, not just the selected one).
+/// Should a mapped device's extent change through its Rust API (e.g. adding a
+/// larger bank), call [`Bus::invalidate`] to rebuild the table. Addresses
+/// missing from the table always fall back to a full walk.
+///
 /// [bus]: https://en.wikipedia.org/wiki/Bus_(computing)
 #[derive(Debug, Default)]
 pub struct Bus {
-    maps: BTreeMap<usize, Vec<SharedDevice>>,
+    maps: BTreeMap<usize, Vec<Mapping>>,
+    open: OpenBus,
+    last: u8,
+    table: Option<page::Table>,
+    watches: watch::Watches,
 }
 
 impl Bus {
@@ -34,14 +73,110 @@ impl Bus {
         Self::default()
     }
 
+    /// Constructs a new, empty `Bus` with the provided open-bus policy.
+    pub fn with(open: OpenBus) -> Self {
+        Self {
+            open,
+            ..Default::default()
+        }
+    }
+
+    /// Gets the open-bus policy.
+    #[must_use]
+    pub fn open_bus(&self) -> &OpenBus {
+        &self.open
+    }
+
+    /// Sets the open-bus policy.
+    pub fn set_open_bus(&mut self, open: OpenBus) {
+        self.open = open;
+    }
+
+    /// Gets the page size of the page-table decoder, if enabled.
+    #[must_use]
+    pub fn page_size(&self) -> Option<usize> {
+        self.table.as_ref().map(page::Table::size)
+    }
+
+    /// Enables (with `Some`) or disables (with `None`) the page-table decoder,
+    /// using pages of the provided size in bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the page size is not a power of two.
+    pub fn set_page_size(&mut self, size: Option<usize>) {
+        let table = size.map(|size| {
+            let devs = self.priority().map(|(base, map)| (base, &map.dev));
+            page::Table::new(size, devs)
+        });
+        self.table = table;
+    }
+
+    /// Rebuilds the page-table decoder, if enabled.
+    ///
+    /// This is performed automatically whenever devices are mapped or
+    /// unmapped, and should only be needed after a mapped device changes its
+    /// extent.
+    pub fn invalidate(&mut self) {
+        self.set_page_size(self.page_size());
+    }
+
+    /// Installs a watchpoint, returning its identifier.
+    ///
+    /// Whenever a read or write to the bus triggers the watchpoint, a [`Hit`]
+    /// is recorded. Peeks and pokes never trigger watchpoints.
+    pub fn watch(&mut self, point: Watchpoint) -> usize {
+        self.watches.add(point)
+    }
+
+    /// Removes and returns the watchpoint with the provided identifier.
+    ///
+    /// Returns `None` if no such watchpoint was installed.
+    pub fn unwatch(&mut self, id: usize) -> Option<Watchpoint> {
+        self.watches.remove(id)
+    }
+
+    /// Gets the watchpoint hits recorded since they were last taken.
+    #[must_use]
+    pub fn hits(&self) -> &[Hit] {
+        self.watches.hits()
+    }
+
+    /// Takes the recorded watchpoint hits, clearing them.
+    ///
+    /// A run loop should check for hits after each step, stopping whenever
+    /// any are returned.
+    pub fn take_hits(&mut self) -> Vec<Hit> {
+        self.watches.take()
+    }
+
     /// Clears the bus, removing all devices.
     pub fn clear(&mut self) {
         self.maps.clear();
+        self.invalidate();
     }
 
     /// Maps a device at the provided `base` address in the bus.
     pub fn map(&mut self, base: usize, dev: SharedDevice) {
-        self.maps.entry(base).or_default().push(dev);
+        self.maps
+            .entry(base)
+            .or_default()
+            .push(Mapping { name: None, dev });
+        self.invalidate();
+    }
+
+    /// Maps a device at the provided `base` address in the bus, labelled with
+    /// the provided `name`.
+    ///
+    /// The label takes precedence over the device's own [`Block::name`] when
+    /// resolving paths (see [`Device::path`]).
+    pub fn map_named(&mut self, base: usize, name: &str, dev: SharedDevice) {
+        let name = Some(name.to_string());
+        self.maps
+            .entry(base)
+            .or_default()
+            .push(Mapping { name, dev });
+        self.invalidate();
     }
 
     /// Unmaps and returns the matching device at position `index` within the
@@ -50,34 +185,179 @@ impl Bus {
     /// Returns `None` if no matching device was found (and unmapped).
     pub fn unmap(&mut self, base: usize, dev: &SharedDevice) -> Option<SharedDevice> {
         let devs = self.maps.get_mut(&base)?;
-        let index = devs.iter().position(|this| Rc::ptr_eq(this, dev))?;
-        Some(devs.remove(index))
+        let index = devs.iter().position(|map| Rc::ptr_eq(&map.dev, dev))?;
+        let map = devs.remove(index);
+        self.invalidate();
+        Some(map.dev)
     }
 
-    /// Borrows the `base` and `SharedDevice` mapped at `index`.
-    fn at(&self, index: usize) -> Option<(&usize, &SharedDevice)> {
+    /// Returns an iterator over the mapped regions of the bus, as tuples of
+    /// `(base, len, dev)`.
+    ///
+    /// Regions are yielded in ascending order of their base address. Devices
+    /// mapped at the same base are yielded in the order they were mapped.
+    pub fn regions(&self) -> impl Iterator<Item = (usize, usize, &SharedDevice)> {
         self.maps
-            .range(..=index)
+            .iter()
+            .flat_map(|(&base, devs)| std::iter::repeat(base).zip(devs))
+            .map(|(base, map)| (base, map.dev.borrow().len(), &map.dev))
+    }
+
+    /// Detects overlapping regions within the bus.
+    ///
+    /// Returns every pair of mapped regions whose extents (as reported by
+    /// [`Bus::regions`]) intersect, in order of the shadowing device's
+    /// priority. Note that the shadowed device may still be visible through
+    /// any holes in the shadowing device.
+    #[must_use]
+    pub fn overlaps(&self) -> Vec<Overlap> {
+        let regions: Vec<_> = self
+            .priority()
+            .map(|(base, map)| (base..base.saturating_add(map.dev.borrow().len()), &map.dev))
+            .collect();
+        let mut overlaps = Vec::new();
+        for (i, (upper, dev)) in regions.iter().enumerate() {
+            for (lower, other) in &regions[i + 1..] {
+                let range = upper.start.max(lower.start)..upper.end.min(lower.end);
+                if !range.is_empty() {
+                    overlaps.push(Overlap {
+                        range,
+                        upper: Rc::clone(dev),
+                        lower: Rc::clone(other),
+                    });
+                }
+            }
+        }
+        overlaps
+    }
+
+    /// Iterates over the mapped devices in priority order: highest base
+    /// first, then in the order they were mapped.
+    fn priority(&self) -> impl Iterator<Item = (usize, &Mapping)> {
+        self.maps
+            .iter()
             .rev()
-            .flat_map(|(base, devs)| std::iter::repeat(base).zip(devs))
-            .find(|(&base, dev)| dev.borrow().contains(index - base))
+            .flat_map(|(&base, devs)| std::iter::repeat(base).zip(devs))
     }
 
-    /// Mutably borrows the `base` and `SharedDevice` mapped at `index`.
-    fn at_mut(&mut self, index: usize) -> Option<(&usize, &mut SharedDevice)> {
+    /// Borrows the `base` and `Mapping` at `index`, bypassing the page table.
+    fn lookup(&self, index: usize) -> Option<(usize, &Mapping)> {
         self.maps
-            .range_mut(..=index)
+            .range(..=index)
             .rev()
-            .flat_map(|(base, devs)| std::iter::repeat(base).zip(devs))
-            .find(|(&base, dev)| dev.borrow().contains(index - base))
+            .flat_map(|(&base, devs)| std::iter::repeat(base).zip(devs))
+            .find(|(base, map)| map.dev.borrow().contains(index - base))
+    }
+
+    /// Borrows the `base` and `SharedDevice` mapped at `index`.
+    fn at(&self, index: usize) -> Option<(usize, &SharedDevice)> {
+        // Consult the page table first, if enabled
+        if let Some(table) = &self.table {
+            let found = table
+                .get(index)
+                .iter()
+                .filter(|(base, _)| *base <= index)
+                .find(|(base, dev)| dev.borrow().contains(index - base));
+            if let Some((base, dev)) = found {
+                return Some((*base, dev));
+            }
+        }
+        self.lookup(index).map(|(base, map)| (base, &map.dev))
+    }
+
+    /// Yields the floating value read at unmapped `index`, according to the
+    /// open-bus policy.
+    fn float(&self, index: usize) -> Result<u8, BusError> {
+        match &self.open {
+            OpenBus::Panic => Err(BusError::Unmapped(index)),
+            OpenBus::Fixed(byte) => Ok(*byte),
+            OpenBus::Last => Ok(self.last),
+            OpenBus::Call(f) => Ok(f(index)),
+        }
+    }
+
+    /// Checks the watchpoints against a completed access.
+    fn check(&mut self, index: usize, access: Access, value: u8) {
+        if self.watches.matches(index, access, value) {
+            let path = self.path(index);
+            self.watches.record(index, access, value, &path);
+        }
+    }
+
+    /// Finds the first address within `index..index + len` at which no device
+    /// is mapped, if any.
+    pub(crate) fn unmapped(&self, index: usize, len: usize) -> Option<usize> {
+        self.runs(index, len)
+            .into_iter()
+            .find_map(|(range, found)| found.is_none().then_some(range.start))
+    }
+
+    /// Splits `index..index + len` into runs of consecutive addresses decoded
+    /// to the same mapped device (as its `base` and `SharedDevice`), or to no
+    /// device at all.
+    fn runs(&self, index: usize, len: usize) -> Vec<Run> {
+        let end = index.saturating_add(len);
+        let mut runs = Vec::new();
+        let mut start = index;
+        while start < end {
+            let Some((base, dev)) = self.at(start) else {
+                // Extend the unmapped run
+                let mut next = start + 1;
+                while next < end && self.at(next).is_none() {
+                    next += 1;
+                }
+                runs.push((start..next, None));
+                start = next;
+                continue;
+            };
+            // Gather the higher-priority devices which may shadow this one
+            // over the remainder of the range
+            let shadows: Vec<_> = self
+                .priority()
+                .take_while(|(this, map)| !(*this == base && Rc::ptr_eq(&map.dev, dev)))
+                .filter(|(this, map)| {
+                    let extent = map.dev.borrow().extent();
+                    let lo = this.saturating_add(extent.start);
+                    let hi = this.saturating_add(extent.end);
+                    lo < end && start < hi
+                })
+                .collect();
+            // Extend the run while it is decoded to the same device
+            let mut next = start + 1;
+            while next < end
+                && dev.borrow().contains(next - base)
+                && !shadows
+                    .iter()
+                    .any(|(this, map)| *this <= next && map.dev.borrow().contains(next - this))
+            {
+                next += 1;
+            }
+            runs.push((start..next, Some((base, dev.clone()))));
+            start = next;
+        }
+        runs
+    }
+
+    /// Borrows the `base` and `SharedDevice` mapped at `index`, provided every
+    /// address in `index..index + len` is decoded to it.
+    fn span(&self, index: usize, len: usize) -> Option<(usize, &SharedDevice)> {
+        let (base, dev) = self.at(index)?;
+        (index + 1..index + len)
+            .all(|index| match self.at(index) {
+                Some((this, other)) => this == base && Rc::ptr_eq(other, dev),
+                None => false,
+            })
+            .then_some((base, dev))
     }
 }
 
 impl Block for Bus {
     fn reset(&mut self) {
-        for dev in self.maps.iter().flat_map(|(_, devs)| devs) {
-            dev.borrow_mut().reset();
+        self.last = 0;
+        for map in self.maps.values().flatten() {
+            map.dev.borrow_mut().reset();
         }
+        self.invalidate();
     }
 }
 
@@ -95,21 +375,281 @@ impl Device for Bus {
             .maps
             .iter()
             .flat_map(|(base, devs)| std::iter::repeat(base).zip(devs))
-            .map(|(&base, dev)| base + dev.borrow().len())
+            .map(|(&base, map)| base + map.dev.borrow().len())
             .max()
             .unwrap_or(0);
         end.saturating_sub(start)
     }
 
-    fn read(&self, index: usize) -> u8 {
-        let (base, dev) = self.at(index).unwrap();
-        dev.borrow().read(index - base)
+    /// Spans from the lowest to the highest address contained by any mapped
+    /// device, which need not start at zero.
+    fn extent(&self) -> Range<usize> {
+        self.regions()
+            .map(|(base, _, dev)| {
+                let Range { start, end } = dev.borrow().extent();
+                base.saturating_add(start)..base.saturating_add(end)
+            })
+            .fold(0..0, dev::union)
     }
 
+    /// Peeks are forwarded to the mapped device, and do not affect the value
+    /// last seen on the data bus.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no device is mapped at `index` under the [`OpenBus::Panic`]
+    /// policy.
+    fn peek(&self, index: usize) -> u8 {
+        match self.at(index) {
+            Some((base, dev)) => dev.borrow().peek(index - base),
+            None => self
+                .float(index)
+                .unwrap_or_else(|err| panic!("`<Bus as Device>::peek()`: {err}")),
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if no device is mapped at `index` under the [`OpenBus::Panic`]
+    /// policy.
     fn write(&mut self, index: usize, byte: u8) {
-        let (base, dev) = self.at_mut(index).unwrap();
-        dev.borrow_mut().write(index - base, byte);
+        self.try_write(index, byte)
+            .unwrap_or_else(|err| panic!("`<Bus as Device>::write()`: {err}"));
     }
+
+    /// # Panics
+    ///
+    /// Panics if no device is mapped at `index` under the [`OpenBus::Panic`]
+    /// policy.
+    fn read(&mut self, index: usize) -> u8 {
+        self.try_read(index)
+            .unwrap_or_else(|err| panic!("`<Bus as Device>::read()`: {err}"))
+    }
+
+    /// Pokes are forwarded to the mapped device, and do not affect the value
+    /// last seen on the data bus.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no device is mapped at `index` under the [`OpenBus::Panic`]
+    /// policy.
+    fn poke(&mut self, index: usize, value: u8) {
+        match self.at(index) {
+            Some((base, dev)) => dev.borrow_mut().poke(index - base, value),
+            None => {
+                if let OpenBus::Panic = self.open {
+                    panic!("`<Bus as Device>::poke()`: {}", BusError::Unmapped(index));
+                }
+            }
+        }
+    }
+
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        let byte = match self.at(index) {
+            Some((base, dev)) => {
+                let byte = dev
+                    .borrow_mut()
+                    .try_read(index - base)
+                    .map_err(|err| err.with_addr(index))?;
+                self.last = byte;
+                byte
+            }
+            None => self.float(index)?,
+        };
+        self.check(index, Access::Read, byte);
+        Ok(byte)
+    }
+
+    fn try_write(&mut self, index: usize, byte: u8) -> Result<(), BusError> {
+        match self.at(index) {
+            Some((base, dev)) => dev
+                .borrow_mut()
+                .try_write(index - base, byte)
+                .map_err(|err| err.with_addr(index))?,
+            None if matches!(self.open, OpenBus::Panic) => {
+                return Err(BusError::Unmapped(index));
+            }
+            // Dropped writes still drive the data bus
+            None => (),
+        }
+        self.last = byte;
+        self.check(index, Access::Write, byte);
+        Ok(())
+    }
+
+    /// Wide accesses decoded entirely to a single device are forwarded to it
+    /// as-is, otherwise they are performed byte-by-byte.
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        match self.span(index, buf.len()) {
+            Some((base, dev)) => {
+                dev.borrow_mut().read_bytes(index - base, buf);
+                if let Some(&byte) = buf.last() {
+                    self.last = byte;
+                }
+                for (offset, &byte) in buf.iter().enumerate() {
+                    self.check(index + offset, Access::Read, byte);
+                }
+            }
+            None => {
+                for (offset, byte) in buf.iter_mut().enumerate() {
+                    *byte = self.read(index + offset);
+                }
+            }
+        }
+    }
+
+    /// Wide accesses decoded entirely to a single device are forwarded to it
+    /// as-is, otherwise they are performed byte-by-byte.
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        match self.span(index, buf.len()) {
+            Some((base, dev)) => {
+                dev.borrow_mut().write_bytes(index - base, buf);
+                if let Some(&byte) = buf.last() {
+                    self.last = byte;
+                }
+                for (offset, &byte) in buf.iter().enumerate() {
+                    self.check(index + offset, Access::Write, byte);
+                }
+            }
+            None => {
+                for (offset, &byte) in buf.iter().enumerate() {
+                    self.write(index + offset, byte);
+                }
+            }
+        }
+    }
+
+    /// Runs of bytes decoded to a single device are forwarded to it as-is.
+    /// Errors raised by mapped devices are rebased into the bus's address
+    /// space.
+    fn try_write_bytes(&mut self, index: usize, buf: &[u8]) -> Result<(), BusError> {
+        for (range, found) in self.runs(index, buf.len()) {
+            let data = &buf[range.start - index..range.end - index];
+            match found {
+                Some((base, dev)) => {
+                    let res = dev.borrow_mut().try_write_bytes(range.start - base, data);
+                    if let Err(err) = res {
+                        // Devices may report translated addresses, so locate
+                        // the failing address by retrying byte-by-byte
+                        for (addr, &byte) in range.clone().zip(data) {
+                            dev.borrow_mut()
+                                .try_write(addr - base, byte)
+                                .map_err(|err| err.with_addr(addr))?;
+                        }
+                        return Err(err.with_addr(range.start));
+                    }
+                }
+                None if matches!(self.open, OpenBus::Panic) => {
+                    return Err(BusError::Unmapped(range.start));
+                }
+                // Dropped writes still drive the data bus
+                None => (),
+            }
+            if let Some(&byte) = data.last() {
+                self.last = byte;
+            }
+            for (addr, &byte) in range.zip(data) {
+                self.check(addr, Access::Write, byte);
+            }
+        }
+        Ok(())
+    }
+
+    fn path(&self, index: usize) -> Vec<String> {
+        match self.lookup(index) {
+            Some((base, map)) => dev::resolve(&map.dev, map.name.as_deref(), index - base),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Prints a memory map table of the bus.
+///
+/// Each mapped region is listed with its start address, (inclusive) end
+/// address, size, and device type, as determined by its [`Debug`]
+/// implementation. Named devices are listed along with their name.
+impl Display for Bus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Display addresses formatted to maximum width
+        let width = self
+            .regions()
+            .map(|(base, len, _)| format!("{:#x}", base + len).len())
+            .max()
+            .unwrap_or_default()
+            .max("start".len());
+
+        // Write table header
+        write!(
+            f,
+            "{:width$} {:width$} {:width$} device",
+            "start", "end", "size"
+        )?;
+        // Write table rows
+        for (base, map) in self
+            .maps
+            .iter()
+            .flat_map(|(&base, devs)| std::iter::repeat(base).zip(devs))
+        {
+            writeln!(f)?;
+            let dev = map.dev.borrow();
+            let len = dev.len();
+            let end = match len {
+                0 => String::from("-"),
+                len => format!("{:#0width$x}", base + len - 1),
+            };
+            write!(f, "{base:#0width$x} {end:>width$} {len:#0width$x} ")?;
+            match map.name.as_deref().or_else(|| dev.name()) {
+                Some(name) => write!(f, "{name} ({})", type_name(&*dev))?,
+                None => write!(f, "{}", type_name(&*dev))?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Device mapped within a [`Bus`].
+#[derive(Debug)]
+struct Mapping {
+    name: Option<String>,
+    dev: SharedDevice,
+}
+
+/// Overlap between two regions of a [`Bus`].
+#[derive(Debug)]
+pub struct Overlap {
+    /// Overlapping address range.
+    pub range: Range<usize>,
+    /// Device which takes priority within the range.
+    pub upper: SharedDevice,
+    /// Device which is shadowed within the range.
+    pub lower: SharedDevice,
+}
+
+/// Extracts the type name of a device from its [`Debug`] representation.
+fn type_name(dev: &dyn Device) -> String {
+    /// Writer which captures only the leading identifier.
+    struct Ident(String);
+
+    impl Write for Ident {
+        fn write_str(&mut self, s: &str) -> std::fmt::Result {
+            match s.find(|c: char| !(c.is_alphanumeric() || c == '_')) {
+                Some(end) => {
+                    self.0.push_str(&s[..end]);
+                    // Stop formatting the remainder of the device
+                    Err(std::fmt::Error)
+                }
+                None => {
+                    self.0.push_str(s);
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    let mut ident = Ident(String::new());
+    let _ = write!(ident, "{dev:?}");
+    ident.0
 }
 
 impl<const N: usize> From<[(usize, SharedDevice); N]> for Bus {
@@ -125,7 +665,37 @@ impl<const N: usize> From<[(usize, SharedDevice); N]> for Bus {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::mem::Ram;
+    use crate::mem::{Ram, Rom};
+    use crate::reg::Register;
+
+    /// Device which counts its reads.
+    #[derive(Debug, Default)]
+    struct Counter(u8);
+
+    impl Block for Counter {}
+
+    impl Device for Counter {
+        fn contains(&self, index: usize) -> bool {
+            index == 0
+        }
+
+        fn len(&self) -> usize {
+            1
+        }
+
+        fn peek(&self, _index: usize) -> u8 {
+            self.0
+        }
+
+        fn write(&mut self, _index: usize, value: u8) {
+            self.0 = value;
+        }
+
+        fn read(&mut self, _index: usize) -> u8 {
+            self.0 += 1;
+            self.0 - 1
+        }
+    }
 
     fn setup() -> Bus {
         Bus::from([
@@ -269,6 +839,19 @@ mod tests {
         assert!((A5..N5).all(|addr| bus.contains(addr)));
     }
 
+    #[test]
+    fn device_extent_works() {
+        let mut bus = Bus::new();
+        assert_eq!(bus.extent(), 0..0);
+        let mut sub = Bus::new();
+        sub.map(0x600, Ram::<0x100>::new().to_shared());
+        bus.map(0x800, sub.to_shared());
+        assert_eq!(bus.len(), 0x100);
+        assert_eq!(bus.extent(), 0xe00..0xf00);
+        bus.map(0x000, Ram::<0x100>::new().to_shared());
+        assert_eq!(bus.extent(), 0x000..0xf00);
+    }
+
     #[test]
     fn device_len_works() {
         // Let's create a mapping where a mapped sub-device has holes that
@@ -347,7 +930,7 @@ mod tests {
 
     #[test]
     fn device_read_mapped_works() {
-        let bus = setup();
+        let mut bus = setup();
         (0x000..0x100).for_each(|i| assert_eq!(bus.read(i), 0));
         (0x100..0x200).for_each(|i| assert_eq!(bus.read(i), 1));
         (0x200..0x300).for_each(|i| assert_eq!(bus.read(i), 2));
@@ -356,10 +939,148 @@ mod tests {
     #[test]
     #[should_panic]
     fn device_read_unmapped_panics() {
-        let bus = setup();
+        let mut bus = setup();
         bus.read(0x301);
     }
 
+    #[test]
+    fn device_try_read_works() {
+        let mut bus = setup();
+        (0x000..0x100).for_each(|i| assert_eq!(bus.try_read(i), Ok(0)));
+        (0x100..0x200).for_each(|i| assert_eq!(bus.try_read(i), Ok(1)));
+        (0x200..0x300).for_each(|i| assert_eq!(bus.try_read(i), Ok(2)));
+        assert_eq!(bus.try_read(0x301), Err(BusError::Unmapped(0x301)));
+    }
+
+    #[test]
+    fn device_try_write_works() {
+        let mut bus = setup();
+        (0x000..0x300).for_each(|i| assert_eq!(bus.try_write(i, 4), Ok(())));
+        (0x000..0x300).for_each(|i| assert_eq!(bus.read(i), 4));
+        assert_eq!(bus.try_write(0x301, 4), Err(BusError::Unmapped(0x301)));
+    }
+
+    #[test]
+    fn device_try_write_read_only_errs() {
+        let mut bus = Bus::from([(0x100, Rom::<0x100>::new().to_shared())]);
+        assert_eq!(bus.try_write(0x180, 4), Err(BusError::ReadOnly(0x180)));
+    }
+
+    #[test]
+    fn device_try_write_bytes_works() {
+        let ram = Ram::<0x100>::new().to_shared();
+        let mut bus = Bus::new();
+        bus.map(0x000, ram.clone());
+        bus.map(0x080, Ram::<0x10>::from(&[0xaa; 0x10]).to_shared());
+        bus.try_write_bytes(0x070, &[0xbb; 0x30]).unwrap();
+        // Writes are split around the shadowing device
+        assert_eq!(ram.borrow().peek(0x07f), 0xbb);
+        assert_eq!(ram.borrow().peek(0x080), 0x00);
+        assert_eq!(ram.borrow().peek(0x090), 0xbb);
+        assert_eq!(bus.read(0x085), 0xbb);
+    }
+
+    #[test]
+    fn device_try_write_bytes_errs() {
+        use crate::bus::adapt::Remap;
+
+        let rom = Rom::<0x100>::new().to_shared();
+        let mut bus = Bus::new();
+        bus.map(0x000, Ram::<0x100>::new().to_shared());
+        bus.map(0x100, rom.clone());
+        assert_eq!(
+            bus.try_write_bytes(0x0f0, &[0; 0x20]),
+            Err(BusError::ReadOnly(0x100))
+        );
+        assert_eq!(
+            bus.try_write_bytes(0x1f0, &[0; 0x20]),
+            Err(BusError::ReadOnly(0x1f0))
+        );
+        bus.unmap(0x100, &rom);
+        assert_eq!(
+            bus.try_write_bytes(0x0f0, &[0; 0x20]),
+            Err(BusError::Unmapped(0x100))
+        );
+        // Errors are rebased even when raised through translating adapters
+        let remap = Remap::new(0x10, Rom::<0x100>::new().to_shared());
+        bus.map(0x8000, remap.to_shared());
+        assert_eq!(bus.try_write(0x8020, 1), Err(BusError::ReadOnly(0x8020)));
+        assert_eq!(
+            bus.try_write_bytes(0x8020, &[1, 2]),
+            Err(BusError::ReadOnly(0x8020))
+        );
+    }
+
+    #[test]
+    fn device_try_write_nested_errs() {
+        // Errors are rebased into the outermost bus's address space
+        let sub = Bus::from([(0x100, Rom::<0x100>::new().to_shared())]);
+        let mut bus = Bus::from([(0x1000, sub.to_shared())]);
+        assert_eq!(bus.try_write(0x1180, 4), Err(BusError::ReadOnly(0x1180)));
+        assert_eq!(bus.try_read(0x1000), Err(BusError::Unmapped(0x1000)));
+    }
+
+    #[test]
+    fn device_read_write_open_bus_works() {
+        // Fixed byte
+        let mut bus = setup();
+        bus.set_open_bus(OpenBus::Fixed(0xff));
+        assert_eq!(bus.read(0x301), 0xff);
+        bus.write(0x301, 4);
+        assert_eq!(bus.read(0x301), 0xff);
+
+        // Last value on the data bus
+        let mut bus = setup();
+        bus.set_open_bus(OpenBus::Last);
+        assert_eq!(bus.read(0x200), 2);
+        assert_eq!(bus.read(0x301), 2);
+        bus.write(0x000, 4);
+        assert_eq!(bus.read(0x301), 4);
+        bus.write(0x301, 5);
+        assert_eq!(bus.read(0x301), 5);
+
+        // Function call
+        let mut bus = Bus::with(OpenBus::Call(Rc::new(|index| index as u8)));
+        assert_eq!(bus.read(0x301), 0x01);
+        assert_eq!(bus.try_read(0x3ff), Ok(0xff));
+    }
+
+    #[test]
+    fn device_read_write_holes_open_bus_works() {
+        // Let's create a mapping where a mapped sub-device has holes that
+        // are left floating:
+        //     ┌──────────────────────────────────┐
+        // D0: │                 11111111    2222 │
+        //     ├──────────────────────────────────┤
+        //     │ ................11111111....2222 │
+        //     └──────────────────────────────────┘
+        let mut bus = Bus::with(OpenBus::Fixed(0xff));
+        // Add device 0
+        let mut d0 = Bus::new();
+        d0.map(0x0000, Ram::<0x0400>::from(&[0xbb; 0x0400]).to_shared());
+        d0.map(0x0600, Ram::<0x0200>::from(&[0xcc; 0x0200]).to_shared());
+        bus.map(0x0800, d0.to_shared());
+
+        // Check if it is accessed properly...
+        assert!((0x0000..=0x07ff)
+            .map(|addr| bus.read(addr))
+            .all(|byte| byte == 0xff));
+        assert!((0x0800..=0x0bff)
+            .map(|addr| bus.read(addr))
+            .all(|byte| byte == 0xbb));
+        assert!((0x0c00..=0x0dff)
+            .map(|addr| bus.read(addr))
+            .all(|byte| byte == 0xff));
+        assert!((0x0e00..=0x0fff)
+            .map(|addr| bus.read(addr))
+            .all(|byte| byte == 0xcc));
+        // Writes to holes should be dropped
+        (0x0c00..=0x0dff).for_each(|addr| bus.write(addr, 0xaa));
+        assert!((0x0c00..=0x0dff)
+            .map(|addr| bus.read(addr))
+            .all(|byte| byte == 0xff));
+    }
+
     #[test]
     fn device_write_mapped_works() {
         let mut bus = setup();
@@ -474,4 +1195,476 @@ mod tests {
             .map(|addr| bus.read(addr))
             .all(|byte| byte == 0xff));
     }
+
+    #[test]
+    fn set_page_size_works() {
+        let mut bus = setup();
+        assert_eq!(bus.page_size(), None);
+        bus.set_page_size(Some(0x40));
+        assert_eq!(bus.page_size(), Some(0x40));
+        bus.set_page_size(None);
+        assert_eq!(bus.page_size(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_page_size_not_power_of_two_panics() {
+        let mut bus = setup();
+        bus.set_page_size(Some(0x30));
+    }
+
+    #[test]
+    fn device_read_write_paged_works() {
+        // Let's create a relatively complicated overlapping bus, with a
+        // mapped sub-device that has holes:
+        //     ┌─────────────────────────────────────────────────┐
+        // D0: │                 0                               │
+        // D1: │                  11                             │
+        // D2: │                    2222                         │
+        // D3: │                        333333333                │
+        // D4: │ 4444444444444444                                │
+        // D5: │                                 55    66        │
+        //     ├─────────────────────────────────────────────────┤
+        //     │ 44444444444444440112222333333333........        │
+        //     └─────────────────────────────────────────────────┘
+        let build = || {
+            let mut bus = Bus::new();
+            bus.map(0x1000, Ram::<0x00>::from(&[0xaa; 0x00]).to_shared());
+            bus.map(0x1000, Ram::<0x01>::from(&[0xbb; 0x01]).to_shared());
+            bus.map(0x1001, Ram::<0x10>::from(&[0xcc; 0x10]).to_shared());
+            bus.map(0x1011, Ram::<0x100>::from(&[0xdd; 0x100]).to_shared());
+            bus.map(0x0000, Ram::<0x1000>::from(&[0xee; 0x1000]).to_shared());
+            let mut d5 = Bus::new();
+            d5.map(0x000, Ram::<0x80>::from(&[0x55; 0x80]).to_shared());
+            d5.map(0x180, Ram::<0x80>::from(&[0x66; 0x80]).to_shared());
+            bus.map(0x1111, d5.to_shared());
+            bus
+        };
+        let mut unpaged = build();
+        for size in [0x1, 0x10, 0x100, 0x1000, 0x10000] {
+            let mut paged = build();
+            paged.set_page_size(Some(size));
+            // Check if it is decoded identically...
+            for addr in 0x0000..0x2000 {
+                assert_eq!(paged.contains(addr), unpaged.contains(addr));
+                assert_eq!(paged.try_read(addr), unpaged.try_read(addr));
+            }
+        }
+
+        // Check nested buses whose devices don't start at zero...
+        let build = || {
+            let mut bus = Bus::new();
+            bus.map(0x000, Ram::<0x1000>::from(&[0xaa; 0x1000]).to_shared());
+            let mut sub = Bus::new();
+            sub.map(0x600, Ram::<0x100>::from(&[0xcc; 0x100]).to_shared());
+            bus.map(0x800, sub.to_shared());
+            bus
+        };
+        let mut unpaged = build();
+        assert_eq!(unpaged.read(0xe00), 0xcc);
+        for size in [0x1, 0x10, 0x100, 0x1000, 0x10000] {
+            let mut paged = build();
+            paged.set_page_size(Some(size));
+            for addr in 0x0000..0x2000 {
+                assert_eq!(paged.contains(addr), unpaged.contains(addr));
+                assert_eq!(paged.try_read(addr), unpaged.try_read(addr));
+            }
+        }
+
+        // Check that sparse address spaces are cheap to page...
+        let mut bus = Bus::new();
+        bus.map(0x8000_0000, Ram::<0x100>::from(&[0xaa; 0x100]).to_shared());
+        bus.set_page_size(Some(0x10));
+        assert_eq!(bus.read(0x8000_00ff), 0xaa);
+        assert!(!bus.contains(0x8000_0100));
+
+        // Check that remapping invalidates the table...
+        let mut bus = Bus::new();
+        bus.set_page_size(Some(0x100));
+        let d0 = Ram::<0x100>::from(&[0xaa; 0x100]).to_shared();
+        bus.map(0x000, d0.clone());
+        assert_eq!(bus.read(0x080), 0xaa);
+        bus.map(0x080, Ram::<0x100>::from(&[0xbb; 0x100]).to_shared());
+        assert_eq!(bus.read(0x080), 0xbb);
+        assert_eq!(bus.read(0x17f), 0xbb);
+        bus.write(0x000, 0xcc);
+        assert_eq!(d0.borrow_mut().read(0x000), 0xcc);
+        bus.unmap(0x000, &d0);
+        assert_eq!(bus.try_read(0x000), Err(BusError::Unmapped(0x000)));
+        bus.clear();
+        assert_eq!(bus.try_read(0x080), Err(BusError::Unmapped(0x080)));
+    }
+
+    #[test]
+    fn device_read_write_word_works() {
+        let mut bus = setup();
+        let reg = Register::<u16>::from(0x0123).to_shared();
+        bus.map(0x300, reg.clone());
+        // Contained within a single device
+        assert_eq!(bus.read_u16_le(0x300), 0x0123);
+        assert_eq!(bus.read_u16_be(0x300), 0x2301);
+        bus.write_u16_le(0x300, 0x4567);
+        assert_eq!(reg.borrow_mut().read_u16_le(0), 0x4567);
+        // Spanning multiple devices
+        assert_eq!(bus.read_u32_le(0x0fe), 0x01010000);
+        assert_eq!(bus.read_u32_be(0x2fe), 0x02026745);
+        bus.write_u32_be(0x1fe, 0x01234567);
+        assert_eq!(bus.read(0x1fe), 0x01);
+        assert_eq!(bus.read(0x1ff), 0x23);
+        assert_eq!(bus.read(0x200), 0x45);
+        assert_eq!(bus.read(0x201), 0x67);
+    }
+
+    #[test]
+    #[should_panic]
+    fn device_read_word_unmapped_panics() {
+        let mut bus = setup();
+        bus.read_u16_le(0x2ff);
+    }
+
+    #[test]
+    fn device_read_side_effects_works() {
+        use crate::bus::adapt::{Bank, View};
+
+        let counter = Counter::default().to_shared();
+        let mut bus = Bus::new();
+        // Map the device directly...
+        bus.map(0x000, counter.clone());
+        // ... and through each adapter
+        bus.map(0x100, View::new(counter.clone(), ..).to_shared());
+        bus.map(0x300, Bank::from(vec![counter.clone()]).to_shared());
+        let mut nested = Bus::new();
+        nested.map(0x000, counter.clone());
+        bus.map(0x400, nested.to_shared());
+
+        // Each read should have its side effect applied exactly once
+        assert_eq!(bus.read(0x000), 0);
+        assert_eq!(bus.read(0x100), 1);
+        assert_eq!(bus.read(0x400), 2);
+        assert_eq!(bus.read(0x300), 3);
+        assert_eq!(bus.try_read(0x100), Ok(4));
+        // Peeks should have no side effects
+        assert_eq!(bus.peek(0x000), 5);
+        assert_eq!(bus.peek(0x300), 5);
+        assert_eq!(counter.borrow().peek(0), 5);
+    }
+
+    #[test]
+    fn device_peek_poke_works() {
+        let mut bus = setup();
+        bus.set_open_bus(OpenBus::Last);
+        bus.map(0x300, Counter::default().to_shared());
+        // Reads have side effects...
+        assert_eq!(bus.read(0x300), 0);
+        assert_eq!(bus.read(0x300), 1);
+        // ... while peeks do not
+        assert_eq!(bus.peek(0x300), 2);
+        assert_eq!(bus.peek(0x300), 2);
+        assert_eq!(bus.read(0x300), 2);
+        // Pokes are forwarded
+        bus.poke(0x300, 0xaa);
+        assert_eq!(bus.peek(0x300), 0xaa);
+        bus.poke(0x000, 0xbb);
+        assert_eq!(bus.peek(0x000), 0xbb);
+        // Open bus is unaffected by peeks and pokes
+        assert_eq!(bus.peek(0x301), 2);
+        bus.poke(0x301, 0xcc);
+        assert_eq!(bus.read(0x301), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn device_peek_unmapped_panics() {
+        let bus = setup();
+        bus.peek(0x301);
+    }
+
+    #[test]
+    fn regions_works() {
+        let bus = setup();
+        let regions: Vec<_> = bus.regions().map(|(base, len, _)| (base, len)).collect();
+        assert_eq!(regions, [(0x000, 0x100), (0x100, 0x100), (0x200, 0x100)]);
+        assert!(bus.overlaps().is_empty());
+    }
+
+    #[test]
+    fn overlaps_works() {
+        // Let's create a relatively complicated overlapping bus:
+        //     ┌─────────────────────────────────────────────────┐
+        // D0: │                 0                               │
+        // D1: │                  11                             │
+        // D2: │                    2222                         │
+        // D3: │ 3333333333333333                                │
+        // D4: │ 44444444444444444444444444444444444444444444... │
+        //     ├─────────────────────────────────────────────────┤
+        //     │ 33333333333333330112222444444444444444444444... │
+        //     └─────────────────────────────────────────────────┘
+        let d0 = Ram::<0x0>::new().to_shared();
+        let d1 = Ram::<0x1>::new().to_shared();
+        let d2 = Ram::<0x10>::new().to_shared();
+        let d3 = Ram::<0x1000>::new().to_shared();
+        let d4 = Ram::<0x2000>::new().to_shared();
+        let bus = Bus::from([
+            (0x1000, d0.clone()),
+            (0x1000, d1.clone()),
+            (0x1001, d2.clone()),
+            (0x0000, d3.clone()),
+            (0x0000, d4.clone()),
+        ]);
+
+        let overlaps: Vec<_> = bus
+            .overlaps()
+            .into_iter()
+            .map(
+                |Overlap {
+                     range,
+                     upper,
+                     lower,
+                 }| {
+                    let upper = [&d1, &d2, &d3].iter().position(|d| Rc::ptr_eq(d, &upper));
+                    let lower = [&d1, &d2, &d3, &d4]
+                        .iter()
+                        .position(|d| Rc::ptr_eq(d, &lower));
+                    (range, upper.unwrap() + 1, lower.unwrap() + 1)
+                },
+            )
+            .collect();
+        assert_eq!(
+            overlaps,
+            [
+                (0x1001..0x1011, 2, 4),
+                (0x1000..0x1001, 1, 4),
+                (0x0000..0x1000, 3, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn display_works() {
+        let mut bus = setup();
+        bus.map(0x300, Register::<u16>::new().to_shared());
+        bus.map(0x400, Bus::new().to_shared());
+        assert_eq!(
+            format!("{bus}"),
+            [
+                r"start end   size  device",
+                r"0x000 0x0ff 0x100 Ram",
+                r"0x100 0x1ff 0x100 Ram",
+                r"0x200 0x2ff 0x100 Ram",
+                r"0x300 0x301 0x002 Register",
+                r"0x400     - 0x000 Bus",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn device_path_works() {
+        use std::cell::RefCell;
+
+        use crate::bus::adapt::{Bank, View};
+
+        // Let's create a cartridge with banked memory behind a mapper:
+        //
+        //     cart
+        //      ├── mbc (View)
+        //      │    └── (Bank)
+        //      │         ├── (Bus) ── rom_bank
+        //      │         └── (Bus) ── ram_bank
+        //      └── ctrl
+        let mut rom = Bus::new();
+        rom.map_named(0x000, "rom_bank", Rom::<0x100>::new().to_shared());
+        let mut ram = Bus::new();
+        ram.map_named(0x000, "ram_bank", Ram::<0x100>::new().to_shared());
+        let bank = Rc::new(RefCell::new(Bank::from(vec![
+            rom.to_shared(),
+            ram.to_shared(),
+        ])));
+        let mut cart = Bus::new();
+        cart.map_named(
+            0x000,
+            "mbc",
+            View::new(bank.clone(), 0x000..0x100).to_shared(),
+        );
+        cart.map_named(0x100, "ctrl", Register::<u8>::new().to_shared());
+        let mut bus = Bus::new();
+        bus.map_named(0x000, "cart", cart.to_shared());
+        bus.map(0x200, Ram::<0x100>::new().to_shared());
+
+        assert_eq!(bus.path(0x080).join("."), "cart.mbc.rom_bank");
+        assert_eq!(bus.path(0x100).join("."), "cart.ctrl");
+        assert_eq!(bus.path(0x200), Vec::<String>::new());
+        assert_eq!(bus.path(0x300), Vec::<String>::new());
+        // Switching banks is reflected in the path
+        bank.borrow_mut().set(1);
+        assert_eq!(bus.path(0x080).join("."), "cart.mbc.ram_bank");
+    }
+
+    #[test]
+    fn display_named_works() {
+        let mut bus = Bus::new();
+        bus.map_named(0x000, "wram", Ram::<0x100>::new().to_shared());
+        bus.map(0x100, Ram::<0x100>::new().to_shared());
+        assert_eq!(
+            format!("{bus}"),
+            [
+                r"start end   size  device",
+                r"0x000 0x0ff 0x100 wram (Ram)",
+                r"0x100 0x1ff 0x100 Ram",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn block_name_works() {
+        /// Device which names itself.
+        #[derive(Debug, Default)]
+        struct Joypad(u8);
+
+        impl Block for Joypad {
+            fn name(&self) -> Option<&str> {
+                Some("joypad")
+            }
+        }
+
+        impl Device for Joypad {
+            fn contains(&self, index: usize) -> bool {
+                index == 0
+            }
+
+            fn len(&self) -> usize {
+                1
+            }
+
+            fn peek(&self, _index: usize) -> u8 {
+                self.0
+            }
+
+            fn write(&mut self, _index: usize, value: u8) {
+                self.0 = value;
+            }
+        }
+
+        let mut io = Bus::new();
+        io.map(0x00, Joypad::default().to_shared());
+        io.map_named(0x01, "p2", Joypad::default().to_shared());
+        // Check the device's own name is used, unless labelled
+        assert_eq!(io.path(0x00), ["joypad"]);
+        assert_eq!(io.path(0x01), ["p2"]);
+        assert_eq!(
+            format!("{io}"),
+            [
+                r"start end   size  device",
+                r"0x000 0x000 0x001 joypad (Joypad)",
+                r"0x001 0x001 0x001 p2 (Joypad)",
+            ]
+            .join("\n")
+        );
+        // Check it is resolved through nested buses
+        let mut bus = Bus::new();
+        bus.map_named(0xff00, "io", io.to_shared());
+        assert_eq!(bus.path(0xff00), ["io", "joypad"]);
+    }
+
+    #[test]
+    fn watch_works() {
+        let mut bus = setup();
+        let rd = bus.watch(Watchpoint::read(0x010..0x020));
+        let wr = bus.watch(Watchpoint::write(0x100..0x110).when(|value| value == 0xaa));
+        let any = bus.watch(Watchpoint::access(0x200..0x201));
+        // Check reads
+        bus.read(0x00f);
+        bus.read(0x010);
+        bus.write(0x011, 0x55);
+        assert_eq!(
+            bus.take_hits(),
+            [Hit {
+                id: rd,
+                index: 0x010,
+                access: Access::Read,
+                value: 0,
+                path: vec![],
+            }]
+        );
+        // Check writes with a predicate
+        bus.write(0x100, 0x55);
+        bus.write(0x101, 0xaa);
+        bus.read(0x101);
+        let hits = bus.take_hits();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(
+            (hits[0].id, hits[0].index, hits[0].value),
+            (wr, 0x101, 0xaa)
+        );
+        // Check accesses
+        bus.read(0x200);
+        bus.write(0x200, 0xbb);
+        let hits = bus.take_hits();
+        assert!(hits.iter().all(|hit| hit.id == any));
+        assert_eq!(
+            hits.iter().map(|hit| hit.access).collect::<Vec<_>>(),
+            [Access::Read, Access::Write]
+        );
+        // Check peeks and pokes are ignored
+        bus.peek(0x200);
+        bus.poke(0x200, 0xcc);
+        assert!(bus.hits().is_empty());
+        // Check wide accesses
+        bus.write_u16_le(0x1ff, 0x1234);
+        assert_eq!(
+            bus.hits()
+                .iter()
+                .map(|hit| (hit.index, hit.value))
+                .collect::<Vec<_>>(),
+            [(0x200, 0x12)]
+        );
+        // Check unwatched
+        assert!(bus.unwatch(any).is_some());
+        assert!(bus.unwatch(any).is_none());
+        bus.take_hits();
+        bus.read(0x200);
+        assert!(bus.hits().is_empty());
+    }
+
+    #[test]
+    fn watch_nested_works() {
+        use crate::bus::adapt::View;
+
+        // Watch an inner bus reached through an adapter
+        let mut inner = Bus::new();
+        inner.map_named(0x000, "wram", Ram::<0x100>::new().to_shared());
+        let inner = Rc::new(std::cell::RefCell::new(inner));
+        let mut bus = Bus::new();
+        bus.map_named(
+            0x800,
+            "io",
+            View::new(inner.clone(), 0x040..0x080).to_shared(),
+        );
+        let outer = bus.watch(Watchpoint::write(0x810..0x811));
+        let id = inner.borrow_mut().watch(Watchpoint::write(0x050..0x051));
+        bus.write(0x810, 0xaa);
+        // Outer bus sees its own addresses
+        assert_eq!(
+            bus.take_hits(),
+            [Hit {
+                id: outer,
+                index: 0x810,
+                access: Access::Write,
+                value: 0xaa,
+                path: vec!["io".to_string(), "wram".to_string()],
+            }]
+        );
+        // Inner bus sees translated addresses
+        assert_eq!(
+            inner.borrow_mut().take_hits(),
+            [Hit {
+                id,
+                index: 0x050,
+                access: Access::Write,
+                value: 0xaa,
+                path: vec!["wram".to_string()],
+            }]
+        );
+    }
 }
@@ -0,0 +1,44 @@
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Value yielded by open-bus reads.
+pub(crate) const OPEN: u8 = 0xff;
+
+/// Open-bus policy.
+///
+/// # Usage
+///
+/// Determines how a [`Bus`](super::Bus) responds to accesses at addresses
+/// where no device is mapped. On real hardware such reads yield "floating"
+/// values, commonly the last byte driven onto the data bus or a fixed pattern
+/// set by pull-up resistors.
+///
+/// Under any policy other than [`OpenBus::Panic`], writes to unmapped
+/// addresses are silently dropped.
+#[derive(Clone, Default)]
+pub enum OpenBus {
+    /// Unmapped accesses are an error.
+    ///
+    /// [`Device::read`](crate::dev::Device::read) and
+    /// [`Device::write`](crate::dev::Device::write) panic, while their
+    /// fallible counterparts return [`BusError::Unmapped`](super::BusError).
+    #[default]
+    Panic,
+    /// Reads yield a fixed byte.
+    Fixed(u8),
+    /// Reads yield the last value seen on the data bus.
+    Last,
+    /// Reads yield the result of calling the function with the address.
+    Call(Rc<dyn Fn(usize) -> u8>),
+}
+
+impl Debug for OpenBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Panic => write!(f, "Panic"),
+            Self::Fixed(byte) => f.debug_tuple("Fixed").field(byte).finish(),
+            Self::Last => write!(f, "Last"),
+            Self::Call(_) => f.debug_tuple("Call").finish_non_exhaustive(),
+        }
+    }
+}
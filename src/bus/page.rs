@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use crate::dev::SharedDevice;
+
+/// Page-table address decoder.
+///
+/// Caches, for each occupied page of the address space, the devices which may
+/// decode addresses within it, in the same priority order as a full walk of
+/// the bus. Each device is assumed to occupy at most its
+/// [`extent`](crate::dev::Device::extent), offset by its base, as reported
+/// when the table was built.
+///
+/// Only occupied pages are stored, such that sparsely populated address
+/// spaces remain cheap to decode.
+#[derive(Debug)]
+pub(super) struct Table {
+    shift: u32,
+    pages: HashMap<usize, Box<[(usize, SharedDevice)]>>,
+}
+
+impl Table {
+    /// Builds a new `Table` with pages of `size` bytes from the provided
+    /// `(base, dev)` mappings, given in priority order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two.
+    pub(super) fn new<'a, I>(size: usize, devs: I) -> Self
+    where
+        I: Iterator<Item = (usize, &'a SharedDevice)>,
+    {
+        assert!(size.is_power_of_two(), "page size must be a power of two");
+        let shift = size.trailing_zeros();
+        let mut pages: HashMap<usize, Vec<(usize, SharedDevice)>> = HashMap::new();
+        for (base, dev) in devs {
+            let extent = dev.borrow().extent();
+            if extent.is_empty() {
+                continue;
+            }
+            let first = base.saturating_add(extent.start) >> shift;
+            let last = base.saturating_add(extent.end - 1) >> shift;
+            for page in first..=last {
+                pages.entry(page).or_default().push((base, dev.clone()));
+            }
+        }
+        Self {
+            shift,
+            pages: pages
+                .into_iter()
+                .map(|(page, devs)| (page, devs.into_boxed_slice()))
+                .collect(),
+        }
+    }
+
+    /// Returns the page size, in bytes.
+    pub(super) fn size(&self) -> usize {
+        1 << self.shift
+    }
+
+    /// Returns the candidate devices for the page containing `index`.
+    pub(super) fn get(&self, index: usize) -> &[(usize, SharedDevice)] {
+        self.pages
+            .get(&(index >> self.shift))
+            .map_or(&[], |page| page)
+    }
+}
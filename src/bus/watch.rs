@@ -0,0 +1,185 @@
+use std::fmt::Debug;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// Kind of memory access.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Access {
+    /// Value read from the bus.
+    Read,
+    /// Value written to the bus.
+    Write,
+}
+
+/// Kind of access which triggers a [`Watchpoint`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Trigger {
+    /// Triggered by reads.
+    Read,
+    /// Triggered by writes.
+    Write,
+    /// Triggered by both reads and writes.
+    Access,
+}
+
+impl Trigger {
+    fn matches(self, access: Access) -> bool {
+        matches!(
+            (self, access),
+            (Self::Read, Access::Read) | (Self::Write, Access::Write) | (Self::Access, _)
+        )
+    }
+}
+
+/// Memory watchpoint.
+///
+/// # Usage
+///
+/// Watchpoints are installed onto a [`Bus`](super::Bus) with
+/// [`Bus::watch`](super::Bus::watch), and trigger whenever an access of the
+/// specified kind is made to an address within their range. Optionally, a
+/// predicate on the accessed value may further restrict when they trigger.
+///
+/// Addresses are those seen by the bus the watchpoint is installed on. As
+/// such, a watchpoint on an outer bus observes accesses regardless of how
+/// they are later decoded by nested buses or adapters.
+#[derive(Clone)]
+pub struct Watchpoint {
+    range: Range<usize>,
+    trigger: Trigger,
+    pred: Option<Rc<dyn Fn(u8) -> bool>>,
+}
+
+impl Watchpoint {
+    /// Constructs a new `Watchpoint` over the provided address range.
+    pub fn new(range: Range<usize>, trigger: Trigger) -> Self {
+        Self {
+            range,
+            trigger,
+            pred: None,
+        }
+    }
+
+    /// Constructs a new `Watchpoint` triggered by reads.
+    pub fn read(range: Range<usize>) -> Self {
+        Self::new(range, Trigger::Read)
+    }
+
+    /// Constructs a new `Watchpoint` triggered by writes.
+    pub fn write(range: Range<usize>) -> Self {
+        Self::new(range, Trigger::Write)
+    }
+
+    /// Constructs a new `Watchpoint` triggered by both reads and writes.
+    pub fn access(range: Range<usize>) -> Self {
+        Self::new(range, Trigger::Access)
+    }
+
+    /// Restricts the watchpoint to trigger only when the accessed value
+    /// satisfies the provided predicate.
+    #[must_use]
+    pub fn when(mut self, pred: impl Fn(u8) -> bool + 'static) -> Self {
+        self.pred = Some(Rc::new(pred));
+        self
+    }
+
+    /// Gets the watched address range.
+    #[must_use]
+    pub fn range(&self) -> &Range<usize> {
+        &self.range
+    }
+
+    /// Gets the kind of access which triggers the watchpoint.
+    #[must_use]
+    pub fn trigger(&self) -> Trigger {
+        self.trigger
+    }
+
+    /// Checks if an access would trigger the watchpoint.
+    #[must_use]
+    pub fn matches(&self, index: usize, access: Access, value: u8) -> bool {
+        self.range.contains(&index)
+            && self.trigger.matches(access)
+            && match &self.pred {
+                Some(pred) => pred(value),
+                None => true,
+            }
+    }
+}
+
+impl Debug for Watchpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Watchpoint")
+            .field("range", &self.range)
+            .field("trigger", &self.trigger)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Record of a triggered [`Watchpoint`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Hit {
+    /// Identifier of the triggered watchpoint.
+    pub id: usize,
+    /// Address of the access.
+    pub index: usize,
+    /// Kind of access.
+    pub access: Access,
+    /// Value read or written.
+    pub value: u8,
+    /// Path of the device which decoded the access (see
+    /// [`Device::path`](crate::dev::Device::path)).
+    pub path: Vec<String>,
+}
+
+/// Installed watchpoints and their recorded hits.
+#[derive(Debug, Default)]
+pub(super) struct Watches {
+    next: usize,
+    points: Vec<(usize, Watchpoint)>,
+    hits: Vec<Hit>,
+}
+
+impl Watches {
+    pub(super) fn add(&mut self, point: Watchpoint) -> usize {
+        let id = self.next;
+        self.next += 1;
+        self.points.push((id, point));
+        id
+    }
+
+    pub(super) fn remove(&mut self, id: usize) -> Option<Watchpoint> {
+        let index = self.points.iter().position(|&(this, _)| this == id)?;
+        Some(self.points.remove(index).1)
+    }
+
+    pub(super) fn hits(&self) -> &[Hit] {
+        &self.hits
+    }
+
+    pub(super) fn take(&mut self) -> Vec<Hit> {
+        std::mem::take(&mut self.hits)
+    }
+
+    /// Checks if an access triggers any watchpoint.
+    pub(super) fn matches(&self, index: usize, access: Access, value: u8) -> bool {
+        self.points
+            .iter()
+            .any(|(_, point)| point.matches(index, access, value))
+    }
+
+    /// Records a hit for each watchpoint triggered by an access.
+    pub(super) fn record(&mut self, index: usize, access: Access, value: u8, path: &[String]) {
+        for (id, point) in &self.points {
+            if point.matches(index, access, value) {
+                self.hits.push(Hit {
+                    id: *id,
+                    index,
+                    access,
+                    value,
+                    path: path.to_vec(),
+                });
+            }
+        }
+    }
+}
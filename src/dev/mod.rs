@@ -5,20 +5,34 @@
 //! The [`Device`] trait is useful in combination with [`Bus`](crate::bus::Bus).
 //! Together, they can be used to emulate the behaviour of [memory-mapped I/O].
 //!
+//! While devices are byte-addressable, multi-byte [`Word`]s can be accessed in
+//! either byte order through methods such as [`Device::read_u16_le`] or
+//! [`DeviceExt::write_word`]. Devices which are able to serve wide accesses
+//! natively should override [`Device::read_bytes`] and
+//! [`Device::write_bytes`], through which all such accesses are performed.
+//!
+//! Reads may have side effects (e.g. popping a FIFO), and so require mutable
+//! access to the device. Debuggers should prefer [`Device::peek`] and
+//! [`Device::poke`], which allow devices to be inspected without triggering
+//! side effects.
+//!
 //! [memory-mapped I/O]: https://en.wikipedia.org/wiki/Memory-mapped_I/O
 
 use std::cell::RefCell;
-use std::ops::DerefMut;
+use std::ops::{DerefMut, Range};
 use std::rc::Rc;
 
 use crate::blk::Block;
+use crate::bus::BusError;
 use crate::mem::Memory;
 
 mod null;
 mod random;
+mod word;
 
 pub use self::null::Null;
 pub use self::random::Random;
+pub use self::word::{Endian, Word};
 
 pub type SharedDevice = Rc<RefCell<dyn Device>>;
 
@@ -36,12 +50,179 @@ pub trait Device: Block {
         self.len() == 0
     }
 
-    /// Reads a byte from the specified address.
-    fn read(&self, index: usize) -> u8;
+    /// Returns the range of addresses which the device may contain.
+    ///
+    /// Every address for which [`Device::contains`] holds must lie within
+    /// this range, including after any state changes the guest may cause
+    /// (e.g. switching banks). It is used by [`Bus`](crate::bus::Bus) to place the device
+    /// within its page table. By default, this is `0..len()`, and so must be
+    /// overridden by devices (e.g. nested buses) which may contain addresses
+    /// beyond their length.
+    fn extent(&self) -> Range<usize> {
+        0..self.len()
+    }
+
+    /// Inspects a byte at the specified address, without side effects.
+    ///
+    /// This is the pure counterpart to [`Device::read`], and is intended for
+    /// use by debuggers and memory viewers.
+    fn peek(&self, index: usize) -> u8;
 
     /// Writes a byte to the specified address.
     fn write(&mut self, index: usize, value: u8);
 
+    /// Reads a byte from the specified address.
+    ///
+    /// Devices whose reads have side effects (e.g. clear-on-read flags, FIFO
+    /// pops) should override this to apply them. By default, this performs a
+    /// [`Device::peek`].
+    fn read(&mut self, index: usize) -> u8 {
+        self.peek(index)
+    }
+
+    /// Modifies a byte at the specified address, without side effects.
+    ///
+    /// Intended for use by debuggers and memory viewers. Devices whose writes
+    /// have side effects (e.g. triggering a transfer) should override this to
+    /// provide a pure modification path. By default, this performs a
+    /// [`Device::write`].
+    fn poke(&mut self, index: usize, value: u8) {
+        self.write(index, value);
+    }
+
+    /// Attempts to read a byte from the specified address.
+    ///
+    /// # Errors
+    ///
+    /// By default, returns [`BusError::OutOfBounds`] if the device does not
+    /// contain `index`. Implementers may report other errors as appropriate.
+    fn try_read(&mut self, index: usize) -> Result<u8, BusError> {
+        if self.contains(index) {
+            Ok(self.read(index))
+        } else {
+            Err(BusError::OutOfBounds(index))
+        }
+    }
+
+    /// Attempts to write a byte to the specified address.
+    ///
+    /// # Errors
+    ///
+    /// By default, returns [`BusError::OutOfBounds`] if the device does not
+    /// contain `index`. Implementers may report other errors as appropriate.
+    fn try_write(&mut self, index: usize, value: u8) -> Result<(), BusError> {
+        if self.contains(index) {
+            self.write(index, value);
+            Ok(())
+        } else {
+            Err(BusError::OutOfBounds(index))
+        }
+    }
+
+    /// Reads consecutive bytes starting at the specified address into `buf`.
+    ///
+    /// By default, this performs a [`Device::read`] for each byte.
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        for (offset, byte) in buf.iter_mut().enumerate() {
+            *byte = self.read(index + offset);
+        }
+    }
+
+    /// Writes consecutive bytes from `buf` starting at the specified address.
+    ///
+    /// By default, this performs a [`Device::write`] for each byte.
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        for (offset, &byte) in buf.iter().enumerate() {
+            self.write(index + offset, byte);
+        }
+    }
+
+    /// Attempts to write consecutive bytes from `buf` starting at the
+    /// specified address.
+    ///
+    /// # Errors
+    ///
+    /// By default, this performs a [`Device::try_write`] for each byte,
+    /// stopping at the first error. Any bytes preceding the error may have
+    /// already been written.
+    fn try_write_bytes(&mut self, index: usize, buf: &[u8]) -> Result<(), BusError> {
+        for (offset, &byte) in buf.iter().enumerate() {
+            self.try_write(index + offset, byte)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a little-endian [`u16`] from the specified address.
+    fn read_u16_le(&mut self, index: usize) -> u16 {
+        read_word(self, index, Endian::Little)
+    }
+
+    /// Reads a big-endian [`u16`] from the specified address.
+    fn read_u16_be(&mut self, index: usize) -> u16 {
+        read_word(self, index, Endian::Big)
+    }
+
+    /// Reads a little-endian [`u32`] from the specified address.
+    fn read_u32_le(&mut self, index: usize) -> u32 {
+        read_word(self, index, Endian::Little)
+    }
+
+    /// Reads a big-endian [`u32`] from the specified address.
+    fn read_u32_be(&mut self, index: usize) -> u32 {
+        read_word(self, index, Endian::Big)
+    }
+
+    /// Reads a little-endian [`u64`] from the specified address.
+    fn read_u64_le(&mut self, index: usize) -> u64 {
+        read_word(self, index, Endian::Little)
+    }
+
+    /// Reads a big-endian [`u64`] from the specified address.
+    fn read_u64_be(&mut self, index: usize) -> u64 {
+        read_word(self, index, Endian::Big)
+    }
+
+    /// Writes a little-endian [`u16`] to the specified address.
+    fn write_u16_le(&mut self, index: usize, value: u16) {
+        write_word(self, index, value, Endian::Little);
+    }
+
+    /// Writes a big-endian [`u16`] to the specified address.
+    fn write_u16_be(&mut self, index: usize, value: u16) {
+        write_word(self, index, value, Endian::Big);
+    }
+
+    /// Writes a little-endian [`u32`] to the specified address.
+    fn write_u32_le(&mut self, index: usize, value: u32) {
+        write_word(self, index, value, Endian::Little);
+    }
+
+    /// Writes a big-endian [`u32`] to the specified address.
+    fn write_u32_be(&mut self, index: usize, value: u32) {
+        write_word(self, index, value, Endian::Big);
+    }
+
+    /// Writes a little-endian [`u64`] to the specified address.
+    fn write_u64_le(&mut self, index: usize, value: u64) {
+        write_word(self, index, value, Endian::Little);
+    }
+
+    /// Writes a big-endian [`u64`] to the specified address.
+    fn write_u64_be(&mut self, index: usize, value: u64) {
+        write_word(self, index, value, Endian::Big);
+    }
+
+    /// Resolves the path of named blocks nested within this device which
+    /// decode the specified address, outermost first.
+    ///
+    /// Unnamed blocks are omitted, and the device's own name is not included.
+    /// Joining the components yields a hierarchical path, such as
+    /// `cart.mbc.rom_bank`. By default, a device has no nested blocks, so this
+    /// returns an empty path.
+    fn path(&self, _index: usize) -> Vec<String> {
+        Vec::new()
+    }
+
     /// Constructs a `SharedDevice` from `self`.
     fn to_shared(self) -> SharedDevice
     where
@@ -63,13 +244,86 @@ where
         <[u8]>::len(self)
     }
 
-    fn read(&self, index: usize) -> u8 {
+    fn peek(&self, index: usize) -> u8 {
         self[index]
     }
 
     fn write(&mut self, index: usize, value: u8) {
         self[index] = value;
     }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        buf.copy_from_slice(&self[index..index + buf.len()]);
+    }
+
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        self[index..index + buf.len()].copy_from_slice(buf);
+    }
+
+    fn try_write_bytes(&mut self, index: usize, buf: &[u8]) -> Result<(), BusError> {
+        match index.checked_add(buf.len()) {
+            Some(end) if end <= self.len() => {
+                self.write_bytes(index, buf);
+                Ok(())
+            }
+            _ => Err(BusError::OutOfBounds(index.max(self.len()))),
+        }
+    }
+}
+
+/// Resolves the path to a nested device, including its own label or name.
+pub(crate) fn resolve(dev: &SharedDevice, label: Option<&str>, index: usize) -> Vec<String> {
+    let dev = dev.borrow();
+    let name = label.or_else(|| dev.name()).map(String::from);
+    name.into_iter().chain(dev.path(index)).collect()
+}
+
+/// Combines the extents of two devices, ignoring any which are empty.
+pub(crate) fn union(a: Range<usize>, b: Range<usize>) -> Range<usize> {
+    match (a.is_empty(), b.is_empty()) {
+        (true, _) => b,
+        (_, true) => a,
+        _ => a.start.min(b.start)..a.end.max(b.end),
+    }
+}
+
+/// Generic [`Word`] access extension for [`Device`].
+///
+/// Implemented for all devices, including `dyn Device`.
+pub trait DeviceExt: Device {
+    /// Reads a word in the specified byte order from the specified address.
+    fn read_word<U: Word>(&mut self, index: usize, endian: Endian) -> U {
+        read_word(self, index, endian)
+    }
+
+    /// Writes a word in the specified byte order to the specified address.
+    fn write_word<U: Word>(&mut self, index: usize, value: U, endian: Endian) {
+        write_word(self, index, value, endian);
+    }
+}
+
+impl<D: Device + ?Sized> DeviceExt for D {}
+
+fn read_word<D, U>(dev: &mut D, index: usize, endian: Endian) -> U
+where
+    D: Device + ?Sized,
+    U: Word,
+{
+    let mut buf = [0; std::mem::size_of::<u128>()];
+    let buf = &mut buf[..U::SIZE];
+    dev.read_bytes(index, buf);
+    U::from_bytes(buf, endian)
+}
+
+fn write_word<D, U>(dev: &mut D, index: usize, value: U, endian: Endian)
+where
+    D: Device + ?Sized,
+    U: Word,
+{
+    let mut buf = [0; std::mem::size_of::<u128>()];
+    let buf = &mut buf[..U::SIZE];
+    value.to_bytes(buf, endian);
+    dev.write_bytes(index, buf);
 }
 
 #[cfg(test)]
@@ -93,4 +347,61 @@ mod tests {
         (0..0x100).for_each(|index| dev.write(index, 0xaa));
         (0..0x100).for_each(|index| assert_eq!(Ram::from(&[0xaau8; 0x100]).read(index), 0xaa));
     }
+
+    #[test]
+    fn device_try_read_works() {
+        let mut dev = Ram::from(&[0xaau8; 0x100]);
+        (0..0x100).for_each(|index| assert_eq!(dev.try_read(index), Ok(0xaa)));
+        assert_eq!(dev.try_read(0x100), Err(BusError::OutOfBounds(0x100)));
+    }
+
+    #[test]
+    fn device_try_write_works() {
+        let mut dev = Ram::from(&[0u8; 0x100]);
+        (0..0x100).for_each(|index| assert_eq!(dev.try_write(index, 0xaa), Ok(())));
+        (0..0x100).for_each(|index| assert_eq!(dev.read(index), 0xaa));
+        assert_eq!(
+            dev.try_write(0x100, 0xaa),
+            Err(BusError::OutOfBounds(0x100))
+        );
+    }
+
+    #[test]
+    fn device_read_word_works() {
+        let mut dev = Ram::from(&[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]);
+        assert_eq!(dev.read_u16_le(0), 0x2301);
+        assert_eq!(dev.read_u16_be(0), 0x0123);
+        assert_eq!(dev.read_u32_le(2), 0xab896745);
+        assert_eq!(dev.read_u32_be(2), 0x456789ab);
+        assert_eq!(dev.read_u64_le(0), 0xefcdab8967452301);
+        assert_eq!(dev.read_u64_be(0), 0x0123456789abcdef);
+        assert_eq!(dev.read_word::<u8>(7, Endian::Big), 0xef);
+        // Shared devices are accessed dynamically
+        let dev = dev.to_shared();
+        assert_eq!(dev.borrow_mut().read_word::<u16>(6, Endian::Little), 0xefcd);
+        assert_eq!(dev.borrow_mut().read_u16_be(6), 0xcdef);
+    }
+
+    #[test]
+    fn device_write_word_works() {
+        let mut dev = Ram::<8>::new();
+        dev.write_u16_le(0, 0x0123);
+        assert_eq!(*dev, [0x23, 0x01, 0, 0, 0, 0, 0, 0]);
+        dev.write_u16_be(0, 0x0123);
+        assert_eq!(*dev, [0x01, 0x23, 0, 0, 0, 0, 0, 0]);
+        dev.write_u32_le(4, 0x01234567);
+        assert_eq!(*dev, [0x01, 0x23, 0, 0, 0x67, 0x45, 0x23, 0x01]);
+        dev.write_u32_be(4, 0x01234567);
+        assert_eq!(*dev, [0x01, 0x23, 0, 0, 0x01, 0x23, 0x45, 0x67]);
+        dev.write_u64_le(0, 0x0123456789abcdef);
+        assert_eq!(*dev, [0xef, 0xcd, 0xab, 0x89, 0x67, 0x45, 0x23, 0x01]);
+        dev.write_u64_be(0, 0x0123456789abcdef);
+        assert_eq!(*dev, [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]);
+        dev.write_word(0, 0xaa_u8, Endian::Little);
+        assert_eq!(dev.read(0), 0xaa);
+        // Shared devices are accessed dynamically
+        let dev = dev.to_shared();
+        dev.borrow_mut().write_word(6, 0xbbcc_u16, Endian::Big);
+        assert_eq!(dev.borrow_mut().read_u16_le(6), 0xccbb);
+    }
 }
@@ -44,7 +44,7 @@ impl<const N: usize> Device for Null<N> {
         N
     }
 
-    fn read(&self, _index: usize) -> u8 {
+    fn peek(&self, _index: usize) -> u8 {
         self.0
     }
 
@@ -57,7 +57,7 @@ mod tests {
 
     #[test]
     fn new_works() {
-        let null = Null::<0x100>::new();
+        let mut null = Null::<0x100>::new();
         assert!((0x000..0x100)
             .map(|addr| null.read(addr))
             .all(|byte| byte == 0));
@@ -65,7 +65,7 @@ mod tests {
 
     #[test]
     fn with_works() {
-        let null = Null::<0x100>::with(0xaa);
+        let mut null = Null::<0x100>::with(0xaa);
         assert!((0x000..0x100)
             .map(|addr| null.read(addr))
             .all(|byte| byte == 0xaa));
@@ -110,10 +110,10 @@ mod tests {
 
     #[test]
     fn device_read_works() {
-        let null = Null::<0x100>::with(0xaa);
+        let mut null = Null::<0x100>::with(0xaa);
         assert!((0x000..0x100)
             .map(|addr| null.read(addr))
-            .all(|byte| byte == null.0));
+            .all(|byte| byte == 0xaa));
     }
 
     #[test]
@@ -1,3 +1,6 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
 use super::Device;
 use crate::blk::Block;
 
@@ -8,14 +11,30 @@ use crate::blk::Block;
 /// The `Random` device ignores all writes, and always yields random "garbage"
 /// values when read. This can be useful to allow memory accesses to an unmapped
 /// region of memory without causing a panic.
-#[derive(Debug, Default)]
-pub struct Random<const N: usize>();
+///
+/// Each read advances the device's internal generator, whereas a peek yields
+/// the value that the next read would, without advancing it. For reproducible
+/// values, construct with [`Random::with_seed`].
+#[derive(Debug)]
+pub struct Random<const N: usize>(StdRng);
 
 impl<const N: usize> Random<N> {
     /// Constructs a new `Random`.
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Constructs a `Random` whose generator is seeded with the provided
+    /// value.
+    pub fn with_seed(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<const N: usize> Default for Random<N> {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
 }
 
 impl<const N: usize> Block for Random<N> {}
@@ -29,11 +48,15 @@ impl<const N: usize> Device for Random<N> {
         N
     }
 
-    fn read(&self, _index: usize) -> u8 {
-        rand::random()
+    fn peek(&self, _index: usize) -> u8 {
+        self.0.clone().gen()
     }
 
     fn write(&mut self, _index: usize, _value: u8) {}
+
+    fn read(&mut self, _index: usize) -> u8 {
+        self.0.gen()
+    }
 }
 
 #[cfg(test)]
@@ -45,6 +68,13 @@ mod tests {
         let _ = Random::<0x100>::new();
     }
 
+    #[test]
+    fn with_seed_works() {
+        let mut r0 = Random::<0x100>::with_seed(0xaa);
+        let mut r1 = Random::<0x100>::with_seed(0xaa);
+        assert!((0x000..0x100).all(|addr| r0.read(addr) == r1.read(addr)));
+    }
+
     #[test]
     fn device_contains_works() {
         const N0: usize = 0x0;
@@ -84,12 +114,22 @@ mod tests {
 
     #[test]
     fn device_read_works() {
-        let random = Random::<0x100>::new();
+        let mut random = Random::<0x100>::new();
         (0x000..0x100).for_each(|addr| {
             let _ = random.read(addr);
         });
     }
 
+    #[test]
+    fn device_peek_works() {
+        let mut random = Random::<0x100>::new();
+        (0x000..0x100).for_each(|addr| {
+            let byte = random.peek(addr);
+            assert_eq!(random.peek(addr), byte);
+            assert_eq!(random.read(addr), byte);
+        });
+    }
+
     #[test]
     fn device_write_works() {
         let mut random = Random::<0x100>::new();
@@ -0,0 +1,62 @@
+use std::fmt::Debug;
+
+use num::Unsigned;
+
+/// Byte order of a multi-byte access.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Endian {
+    /// Least significant byte at the lowest address.
+    Little,
+    /// Most significant byte at the lowest address.
+    Big,
+}
+
+/// Multi-byte word.
+///
+/// Implemented for the unsigned integer types [`u8`], [`u16`], [`u32`],
+/// [`u64`], and [`u128`], allowing them to be accessed through
+/// [`DeviceExt::read_word`](super::DeviceExt::read_word) and
+/// [`DeviceExt::write_word`](super::DeviceExt::write_word).
+pub trait Word: Copy + Debug + Unsigned {
+    /// Width of the word, in bytes.
+    const SIZE: usize;
+
+    /// Constructs a word from its bytes in the specified order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not exactly [`Word::SIZE`] bytes long.
+    fn from_bytes(bytes: &[u8], endian: Endian) -> Self;
+
+    /// Writes the bytes of a word in the specified order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not exactly [`Word::SIZE`] bytes long.
+    fn to_bytes(self, bytes: &mut [u8], endian: Endian);
+}
+
+macro_rules! word {
+    ($($U:ty),*) => {$(
+        impl Word for $U {
+            const SIZE: usize = std::mem::size_of::<$U>();
+
+            fn from_bytes(bytes: &[u8], endian: Endian) -> Self {
+                let bytes = bytes.try_into().unwrap();
+                match endian {
+                    Endian::Little => <$U>::from_le_bytes(bytes),
+                    Endian::Big => <$U>::from_be_bytes(bytes),
+                }
+            }
+
+            fn to_bytes(self, bytes: &mut [u8], endian: Endian) {
+                bytes.copy_from_slice(&match endian {
+                    Endian::Little => self.to_le_bytes(),
+                    Endian::Big => self.to_be_bytes(),
+                });
+            }
+        }
+    )*};
+}
+
+word!(u8, u16, u32, u64, u128);
@@ -0,0 +1,149 @@
+use std::fmt::{Debug, Display};
+use std::io::Read;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+
+use crate::blk::Block;
+use crate::mem::load::{self, LoadError};
+use crate::mem::Memory;
+
+/// Dynamically sized random-access memory model.
+///
+/// Behaves identically to [`Ram`](super::Ram), but with a length determined
+/// at runtime (e.g. from a cartridge header).
+#[derive(Debug, Default)]
+pub struct DynRam(Box<[u8]>);
+
+impl DynRam {
+    /// Constructs a new, empty `DynRam` of `len` bytes.
+    pub fn new(len: usize) -> Self {
+        Self(vec![Default::default(); len].into_boxed_slice())
+    }
+
+    /// Constructs a `DynRam` from an entire image read from `reader`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be read.
+    pub fn from_reader(reader: impl Read) -> Result<Self, LoadError> {
+        load::read_all(reader).map(Self::from)
+    }
+
+    /// Constructs a `DynRam` from an entire image read from the file at
+    /// `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, LoadError> {
+        Self::from_reader(load::open(path)?)
+    }
+}
+
+impl Block for DynRam {
+    fn reset(&mut self) {
+        self.0.fill(Default::default());
+    }
+}
+
+impl Memory for DynRam {}
+
+impl Deref for DynRam {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for DynRam {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Display for DynRam {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self as &dyn Memory)
+    }
+}
+
+impl From<&[u8]> for DynRam {
+    fn from(buf: &[u8]) -> Self {
+        Self(buf.into())
+    }
+}
+
+impl From<Vec<u8>> for DynRam {
+    fn from(vec: Vec<u8>) -> Self {
+        Self(vec.into_boxed_slice())
+    }
+}
+
+impl From<Box<[u8]>> for DynRam {
+    fn from(buf: Box<[u8]>) -> Self {
+        Self(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::Device;
+
+    #[test]
+    fn new_works() {
+        let ram = DynRam::new(0x100);
+        assert_eq!(ram.len(), 0x100);
+        assert!(ram.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn from_works() {
+        let vec: Vec<u8> = (0..0x100).map(|x| x as u8).collect();
+        let ram = DynRam::from(&vec[..]);
+        assert_eq!(*ram, vec);
+        let ram = DynRam::from(vec.clone().into_boxed_slice());
+        assert_eq!(*ram, vec);
+        let ram = DynRam::from(vec.clone());
+        assert_eq!(*ram, vec);
+    }
+
+    #[test]
+    fn from_reader_works() {
+        let ram = DynRam::from_reader(&[0xaa; 0x10][..]).unwrap();
+        assert_eq!(*ram, [0xaa; 0x10]);
+    }
+
+    #[test]
+    fn block_reset_works() {
+        let mut ram = DynRam::from(vec![0xaa; 0x10]);
+        ram.reset();
+        assert_eq!(*ram, [0; 0x10]);
+    }
+
+    #[test]
+    fn device_contains_works() {
+        let ram = DynRam::new(0x100);
+        (0x000..0x100).for_each(|addr| assert!(ram.contains(addr)));
+        assert!(!ram.contains(0x100));
+    }
+
+    #[test]
+    fn device_read_write_works() {
+        let mut ram = DynRam::new(0x1);
+        assert_eq!(ram.read(0x0), 0x00);
+        ram.write(0x0, 0xaa);
+        assert_eq!(ram.read(0x0), 0xaa);
+        assert_eq!(
+            ram.try_read(0x1),
+            Err(crate::bus::BusError::OutOfBounds(0x1))
+        );
+    }
+
+    #[test]
+    fn display_works() {
+        let ram = DynRam::from(vec![0x01; 0x4]);
+        assert_eq!(format!("{ram}"), "0x0: 0101 0101");
+    }
+}
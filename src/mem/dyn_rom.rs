@@ -0,0 +1,185 @@
+use std::fmt::{Debug, Display};
+use std::io::Read;
+use std::ops::Deref;
+use std::path::Path;
+
+use crate::blk::Block;
+use crate::bus::BusError;
+use crate::dev::Device;
+use crate::mem::load::{self, LoadError};
+use crate::mem::Memory;
+
+/// Dynamically sized read-only memory model.
+///
+/// Behaves identically to [`Rom`](super::Rom), but with a length determined
+/// at runtime (e.g. from the size of a ROM image).
+#[derive(Debug, Default)]
+pub struct DynRom(Box<[u8]>);
+
+impl DynRom {
+    /// Constructs a new, empty `DynRom` of `len` bytes.
+    pub fn new(len: usize) -> Self {
+        Self(vec![Default::default(); len].into_boxed_slice())
+    }
+
+    /// Constructs a `DynRom` from an entire image read from `reader`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be read.
+    pub fn from_reader(reader: impl Read) -> Result<Self, LoadError> {
+        load::read_all(reader).map(Self::from)
+    }
+
+    /// Constructs a `DynRom` from an entire image read from the file at
+    /// `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, LoadError> {
+        Self::from_reader(load::open(path)?)
+    }
+}
+
+impl Block for DynRom {}
+
+impl Memory for DynRom {}
+
+impl Deref for DynRom {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Device for DynRom {
+    fn contains(&self, index: usize) -> bool {
+        (0..self.len()).contains(&index)
+    }
+
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+
+    fn peek(&self, index: usize) -> u8 {
+        self[index]
+    }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        buf.copy_from_slice(&self[index..index + buf.len()]);
+    }
+
+    /// # Panics
+    ///
+    /// Panics when attempting to write to a [`DynRom`].
+    fn write(&mut self, _index: usize, _value: u8) {
+        panic!("called `Device::write()` on a `DynRom`");
+    }
+
+    fn try_write(&mut self, index: usize, _value: u8) -> Result<(), BusError> {
+        if self.contains(index) {
+            Err(BusError::ReadOnly(index))
+        } else {
+            Err(BusError::OutOfBounds(index))
+        }
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        self.0[index] = value;
+    }
+}
+
+impl Display for DynRom {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self as &dyn Memory)
+    }
+}
+
+impl From<&[u8]> for DynRom {
+    fn from(buf: &[u8]) -> Self {
+        Self(buf.into())
+    }
+}
+
+impl From<Vec<u8>> for DynRom {
+    fn from(vec: Vec<u8>) -> Self {
+        Self(vec.into_boxed_slice())
+    }
+}
+
+impl From<Box<[u8]>> for DynRom {
+    fn from(buf: Box<[u8]>) -> Self {
+        Self(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_works() {
+        let rom = DynRom::new(0x100);
+        assert_eq!(rom.len(), 0x100);
+        assert!(rom.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn from_works() {
+        let vec: Vec<u8> = (0..0x100).map(|x| x as u8).collect();
+        let rom = DynRom::from(&vec[..]);
+        assert_eq!(*rom, vec);
+        let rom = DynRom::from(vec.clone().into_boxed_slice());
+        assert_eq!(*rom, vec);
+        let rom = DynRom::from(vec.clone());
+        assert_eq!(*rom, vec);
+    }
+
+    #[test]
+    fn from_reader_works() {
+        let rom = DynRom::from_reader(&[0xaa; 0x10][..]).unwrap();
+        assert_eq!(*rom, [0xaa; 0x10]);
+    }
+
+    #[test]
+    fn from_path_errs() {
+        assert!(DynRom::from_path("/nonexistent/image.bin").is_err());
+    }
+
+    #[test]
+    fn device_contains_works() {
+        let rom = DynRom::new(0x100);
+        (0x000..0x100).for_each(|addr| assert!(rom.contains(addr)));
+        assert!(!rom.contains(0x100));
+    }
+
+    #[test]
+    fn device_read_word_works() {
+        let mut rom = DynRom::from(vec![0x01, 0x23, 0x45, 0x67]);
+        assert_eq!(rom.read_u32_le(0x0), 0x67452301);
+        assert_eq!(rom.read_u16_be(0x2), 0x4567);
+    }
+
+    #[test]
+    #[should_panic]
+    fn device_write_panics() {
+        let mut rom = DynRom::from(vec![0xaa]);
+        rom.write(0x0, 0xaa);
+    }
+
+    #[test]
+    fn device_poke_works() {
+        let mut rom = DynRom::from(vec![0xaa]);
+        rom.poke(0x0, 0xbb);
+        assert_eq!(rom.read(0x0), 0xbb);
+    }
+
+    #[test]
+    fn device_try_write_errs() {
+        let mut rom = DynRom::from(vec![0xaa]);
+        assert_eq!(rom.try_write(0x0, 0xbb), Err(BusError::ReadOnly(0x0)));
+        assert_eq!(rom.try_write(0x1, 0xbb), Err(BusError::OutOfBounds(0x1)));
+    }
+}
@@ -0,0 +1,536 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use super::{ImageError, Segment};
+use crate::bus::{Bus, BusError};
+use crate::dev::Device;
+
+/// Loadable program segment type.
+const PT_LOAD: u32 = 1;
+/// Symbol table section type.
+const SHT_SYMTAB: u32 = 2;
+/// Section symbol type.
+const STT_SECTION: u8 = 3;
+/// File symbol type.
+const STT_FILE: u8 = 4;
+
+/// Parsed ELF executable.
+///
+/// # Usage
+///
+/// Both 32-bit and 64-bit ELF files of either endianness are supported. Each
+/// `PT_LOAD` program header becomes a [`Segment`] at its physical address,
+/// with any memory beyond the segment's file contents (i.e. BSS) zeroed.
+///
+/// Once parsed, an executable can be loaded into a [`Bus`](crate::bus::Bus)
+/// with [`Elf::load`], returning the entry point with which to start the CPU.
+/// Symbols from the executable's symbol table are available through
+/// [`Elf::symbols`], which may be useful for test harnesses (e.g. to locate a
+/// `tohost` variable).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Elf {
+    machine: u16,
+    entry: usize,
+    segments: Vec<Segment>,
+    symbols: Vec<Symbol>,
+}
+
+impl Elf {
+    /// Parses an ELF executable.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ImageError::Format`] if the file is not a supported ELF
+    /// file, or [`ImageError::Malformed`] if any of its headers or tables are
+    /// out of bounds or inconsistent.
+    pub fn parse(buf: &[u8]) -> Result<Self, ImageError> {
+        // Check the identification
+        let ident = buf.get(..16).ok_or(ImageError::Format)?;
+        if ident[..4] != *b"\x7fELF" || ident[6] != 1 {
+            return Err(ImageError::Format);
+        }
+        let wide = match ident[4] {
+            1 => false,
+            2 => true,
+            _ => return Err(ImageError::Format),
+        };
+        let le = match ident[5] {
+            1 => true,
+            2 => false,
+            _ => return Err(ImageError::Format),
+        };
+        let data = Data { buf, wide, le };
+
+        // Parse the file header
+        let [phoff, shoff, phentsize, phnum, shentsize, shnum] = if wide {
+            [32, 40, 54, 56, 58, 60]
+        } else {
+            [28, 32, 42, 44, 46, 48]
+        };
+        let machine = data.u16(18)?;
+        let entry = data.word(24)?;
+        let phoff = data.word(phoff)?;
+        let shoff = data.word(shoff)?;
+        let phentsize = data.u16(phentsize)? as usize;
+        let phnum = data.u16(phnum)? as usize;
+        let shentsize = data.u16(shentsize)? as usize;
+        let shnum = data.u16(shnum)? as usize;
+
+        // Collect loadable segments
+        let [p_offset, p_paddr, p_filesz, p_memsz] = if wide {
+            [8, 24, 32, 40]
+        } else {
+            [4, 12, 16, 20]
+        };
+        let mut segments = Vec::new();
+        for i in 0..phnum {
+            let ph = data.entry(phoff, i, phentsize)?;
+            if data.u32(ph)? != PT_LOAD {
+                continue;
+            }
+            let offset = data.word(ph + p_offset)?;
+            let addr = data.word(ph + p_paddr)?;
+            let filesz = data.word(ph + p_filesz)?;
+            let memsz = data.word(ph + p_memsz)?;
+            if filesz > memsz || addr.checked_add(memsz).is_none() {
+                return Err(ImageError::Malformed);
+            }
+            // Memory beyond the file contents is zero-filled when loaded
+            if memsz != 0 {
+                segments.push(Segment {
+                    addr,
+                    data: data.bytes(offset, filesz)?.to_vec(),
+                    fill: memsz - filesz,
+                });
+            }
+        }
+
+        // Collect symbols
+        let [sh_offset, sh_size, sh_link, sh_entsize] = if wide {
+            [24, 32, 40, 56]
+        } else {
+            [16, 20, 24, 36]
+        };
+        let mut symbols = Vec::new();
+        for i in 0..shnum {
+            let sh = data.entry(shoff, i, shentsize)?;
+            if data.u32(sh + 4)? != SHT_SYMTAB {
+                continue;
+            }
+            let offset = data.word(sh + sh_offset)?;
+            let size = data.word(sh + sh_size)?;
+            let entsize = data.word(sh + sh_entsize)?;
+            if entsize == 0 {
+                return Err(ImageError::Malformed);
+            }
+            // Locate the linked string table
+            let link = data.entry(shoff, data.u32(sh + sh_link)? as usize, shentsize)?;
+            let strtab = data.bytes(data.word(link + sh_offset)?, data.word(link + sh_size)?)?;
+            // Skip the initial null symbol
+            for j in 1..size / entsize {
+                let st = data.entry(offset, j, entsize)?;
+                let (info, addr, size) = if wide {
+                    (data.u8(st + 4)?, data.word(st + 8)?, data.word(st + 16)?)
+                } else {
+                    (data.u8(st + 12)?, data.word(st + 4)?, data.word(st + 8)?)
+                };
+                if matches!(info & 0xf, STT_SECTION | STT_FILE) {
+                    continue;
+                }
+                let name = strtab
+                    .get(data.u32(st)? as usize..)
+                    .ok_or(ImageError::Malformed)?;
+                let name = name.split(|&byte| byte == 0).next().unwrap_or_default();
+                if name.is_empty() {
+                    continue;
+                }
+                symbols.push(Symbol {
+                    name: String::from_utf8_lossy(name).into_owned(),
+                    addr,
+                    size,
+                });
+            }
+        }
+
+        Ok(Self {
+            machine,
+            entry,
+            segments,
+            symbols,
+        })
+    }
+
+    /// Reads and parses an ELF executable from `reader`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn from_reader(mut reader: impl Read) -> Result<Self, ImageError> {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        Self::parse(&buf)
+    }
+
+    /// Reads and parses an ELF executable from the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ImageError> {
+        Self::parse(&fs::read(path)?)
+    }
+
+    /// Gets the executable's target architecture (`e_machine`).
+    #[must_use]
+    pub fn machine(&self) -> u16 {
+        self.machine
+    }
+
+    /// Gets the executable's entry point.
+    #[must_use]
+    pub fn entry(&self) -> usize {
+        self.entry
+    }
+
+    /// Gets the executable's loadable segments.
+    #[must_use]
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Gets the executable's symbols.
+    #[must_use]
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Looks up a symbol by name.
+    #[must_use]
+    pub fn symbol(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.iter().find(|symbol| symbol.name == name)
+    }
+
+    /// Loads the executable's segments into a bus, returning the entry point.
+    ///
+    /// Unlike [`Image::load`](super::Image::load), segments are loaded with
+    /// [`Device::try_write_bytes`], such that segments landing on read-only
+    /// memory are reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BusError::Unmapped`] if any part of a segment is not mapped,
+    /// or any error raised by a mapped device when writing (e.g.
+    /// [`BusError::ReadOnly`]). Any preceding segments will have already been
+    /// loaded.
+    pub fn load(&self, bus: &mut Bus) -> Result<usize, BusError> {
+        // Zero-fill in bounded chunks
+        const ZEROS: [u8; 0x1000] = [0; 0x1000];
+
+        for Segment { addr, data, fill } in &self.segments {
+            if let Some(index) = bus.unmapped(*addr, data.len() + fill) {
+                return Err(BusError::Unmapped(index));
+            }
+            bus.try_write_bytes(*addr, data)?;
+            let mut index = addr + data.len();
+            let mut fill = *fill;
+            while fill != 0 {
+                let len = fill.min(ZEROS.len());
+                bus.try_write_bytes(index, &ZEROS[..len])?;
+                index += len;
+                fill -= len;
+            }
+        }
+        Ok(self.entry)
+    }
+}
+
+/// Executable symbol.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Symbol {
+    /// Symbol name.
+    pub name: String,
+    /// Symbol address (`st_value`).
+    pub addr: usize,
+    /// Symbol size, in bytes.
+    pub size: usize,
+}
+
+/// Class- and endianness-aware view of an ELF file.
+struct Data<'a> {
+    buf: &'a [u8],
+    wide: bool,
+    le: bool,
+}
+
+impl Data<'_> {
+    fn bytes(&self, offset: usize, len: usize) -> Result<&[u8], ImageError> {
+        offset
+            .checked_add(len)
+            .and_then(|end| self.buf.get(offset..end))
+            .ok_or(ImageError::Malformed)
+    }
+
+    fn array<const N: usize>(&self, offset: usize) -> Result<[u8; N], ImageError> {
+        let mut arr: [u8; N] = self.bytes(offset, N)?.try_into().unwrap();
+        if !self.le {
+            arr.reverse();
+        }
+        Ok(arr)
+    }
+
+    /// Locates the `index`th entry of a table, ensuring it is in bounds.
+    fn entry(&self, table: usize, index: usize, size: usize) -> Result<usize, ImageError> {
+        let offset = index
+            .checked_mul(size)
+            .and_then(|rel| rel.checked_add(table))
+            .ok_or(ImageError::Malformed)?;
+        self.bytes(offset, size).map(|_| offset)
+    }
+
+    fn u8(&self, offset: usize) -> Result<u8, ImageError> {
+        self.array(offset).map(u8::from_le_bytes)
+    }
+
+    fn u16(&self, offset: usize) -> Result<u16, ImageError> {
+        self.array(offset).map(u16::from_le_bytes)
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32, ImageError> {
+        self.array(offset).map(u32::from_le_bytes)
+    }
+
+    /// Reads an address-sized word, according to the file's class.
+    fn word(&self, offset: usize) -> Result<usize, ImageError> {
+        let word = if self.wide {
+            self.array(offset).map(u64::from_le_bytes)?
+        } else {
+            self.array(offset).map(u32::from_le_bytes)? as u64
+        };
+        usize::try_from(word).map_err(|_| ImageError::Malformed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::adapt::Remap;
+    use crate::mem::{Ram, Rom};
+
+    /// Builds an executable with a text segment at 0x1000, a data segment
+    /// (with BSS) at 0x2000, and a symbol table.
+    fn build(wide: bool, le: bool) -> Vec<u8> {
+        let mut buf = Vec::new();
+        let put = |buf: &mut Vec<u8>, value: u64, len: usize| {
+            let bytes = value.to_le_bytes();
+            let mut bytes = bytes[..len].to_vec();
+            if !le {
+                bytes.reverse();
+            }
+            buf.extend(bytes);
+        };
+        let word = if wide { 8 } else { 4 };
+        let (ehsize, phentsize, shentsize, symsize) = if wide {
+            (64, 56, 64, 24)
+        } else {
+            (52, 32, 40, 16)
+        };
+        // Layout
+        let phoff = ehsize;
+        let text = phoff + 2 * phentsize;
+        let data = text + 4;
+        let strtab = data + 2;
+        let names = b"\0_start\0counter\0sect\0";
+        let symtab = strtab + names.len();
+        let shoff = symtab + 4 * symsize;
+
+        // File header
+        buf.extend(b"\x7fELF");
+        buf.extend([if wide { 2 } else { 1 }, if le { 1 } else { 2 }, 1]);
+        buf.resize(16, 0);
+        put(&mut buf, 2, 2); // e_type
+        put(&mut buf, 0xf3, 2); // e_machine
+        put(&mut buf, 1, 4); // e_version
+        put(&mut buf, 0x1000, word); // e_entry
+        put(&mut buf, phoff as u64, word); // e_phoff
+        put(&mut buf, shoff as u64, word); // e_shoff
+        put(&mut buf, 0, 4); // e_flags
+        put(&mut buf, ehsize as u64, 2); // e_ehsize
+        put(&mut buf, phentsize as u64, 2); // e_phentsize
+        put(&mut buf, 2, 2); // e_phnum
+        put(&mut buf, shentsize as u64, 2); // e_shentsize
+        put(&mut buf, 3, 2); // e_shnum
+        put(&mut buf, 0, 2); // e_shstrndx
+        assert_eq!(buf.len(), ehsize);
+
+        // Program headers
+        for (offset, addr, filesz, memsz) in [(text, 0x1000, 4, 4), (data, 0x2000, 2, 6)] {
+            put(&mut buf, PT_LOAD as u64, 4);
+            if wide {
+                put(&mut buf, 0, 4); // p_flags
+            }
+            put(&mut buf, offset as u64, word);
+            put(&mut buf, addr, word); // p_vaddr
+            put(&mut buf, addr, word); // p_paddr
+            put(&mut buf, filesz, word);
+            put(&mut buf, memsz, word);
+            if !wide {
+                put(&mut buf, 0, 4); // p_flags
+            }
+            put(&mut buf, 0, word); // p_align
+        }
+
+        // Segment contents
+        buf.extend([0x01, 0x02, 0x03, 0x04]);
+        buf.extend([0x05, 0x06]);
+
+        // String table
+        buf.extend(names);
+
+        // Symbol table
+        for (name, info, value, size) in [
+            (0, 0, 0, 0),
+            (1, 0x12, 0x1000, 4),
+            (8, 0x11, 0x2002, 4),
+            (16, 0x03, 0x2000, 0),
+        ] {
+            put(&mut buf, name, 4);
+            if wide {
+                put(&mut buf, info, 1);
+                put(&mut buf, 0, 1); // st_other
+                put(&mut buf, 1, 2); // st_shndx
+                put(&mut buf, value, word);
+                put(&mut buf, size, word);
+            } else {
+                put(&mut buf, value, word);
+                put(&mut buf, size, word);
+                put(&mut buf, info, 1);
+                put(&mut buf, 0, 1); // st_other
+                put(&mut buf, 1, 2); // st_shndx
+            }
+        }
+
+        // Section headers
+        let sections = [
+            (0, 0, 0, 0, 0),
+            (SHT_SYMTAB, symtab, 4 * symsize, 2, symsize),
+            (3, strtab, names.len(), 0, 0),
+        ];
+        for (kind, offset, size, link, entsize) in sections {
+            put(&mut buf, 0, 4); // sh_name
+            put(&mut buf, kind as u64, 4);
+            put(&mut buf, 0, word); // sh_flags
+            put(&mut buf, 0, word); // sh_addr
+            put(&mut buf, offset as u64, word);
+            put(&mut buf, size as u64, word);
+            put(&mut buf, link, 4);
+            put(&mut buf, 0, 4); // sh_info
+            put(&mut buf, 0, word); // sh_addralign
+            put(&mut buf, entsize as u64, word);
+        }
+
+        buf
+    }
+
+    #[test]
+    fn parse_works() {
+        for (wide, le) in [(false, true), (false, false), (true, true), (true, false)] {
+            let elf = Elf::parse(&build(wide, le)).unwrap();
+            assert_eq!(elf.machine(), 0xf3);
+            assert_eq!(elf.entry(), 0x1000);
+            assert_eq!(
+                elf.segments(),
+                [
+                    Segment {
+                        addr: 0x1000,
+                        data: vec![0x01, 0x02, 0x03, 0x04],
+                        fill: 0,
+                    },
+                    Segment {
+                        addr: 0x2000,
+                        data: vec![0x05, 0x06],
+                        fill: 4,
+                    },
+                ]
+            );
+            // Section symbols are omitted
+            assert_eq!(elf.symbols().len(), 2);
+            assert_eq!(
+                elf.symbol("counter"),
+                Some(&Symbol {
+                    name: String::from("counter"),
+                    addr: 0x2002,
+                    size: 4,
+                })
+            );
+            assert_eq!(elf.symbol("_start").map(|symbol| symbol.addr), Some(0x1000));
+        }
+    }
+
+    #[test]
+    fn parse_errs() {
+        assert!(matches!(Elf::parse(b"ELF"), Err(ImageError::Format)));
+        assert!(matches!(
+            Elf::parse(b":00000001FF\n\0\0\0\0\0"),
+            Err(ImageError::Format)
+        ));
+        // Truncated tables
+        let buf = build(false, true);
+        assert!(matches!(
+            Elf::parse(&buf[..buf.len() - 1]),
+            Err(ImageError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn parse_large_bss_works() {
+        // BSS is not allocated when parsed
+        let mut buf = build(false, true);
+        buf[104..108].copy_from_slice(&[0xff; 4]);
+        let elf = Elf::parse(&buf).unwrap();
+        assert_eq!(elf.segments()[1].fill, 0xffff_fffd);
+        // Segments must not overflow the address space
+        let mut buf = build(true, true);
+        buf[160..168].copy_from_slice(&[0xff; 8]);
+        assert!(matches!(Elf::parse(&buf), Err(ImageError::Malformed)));
+    }
+
+    #[test]
+    fn load_works() {
+        let elf = Elf::parse(&build(true, true)).unwrap();
+        let text = Ram::<0x100>::new().to_shared();
+        let data = Ram::<0x100>::from(&[0xff; 0x100]).to_shared();
+        let mut bus = Bus::new();
+        bus.map(0x1000, text);
+        bus.map(0x2000, data);
+        assert_eq!(elf.load(&mut bus), Ok(0x1000));
+        assert_eq!(bus.read_u32_le(0x1000), 0x04030201);
+        // BSS is zeroed
+        assert_eq!(bus.read_u32_le(0x2002), 0x00000000);
+        assert_eq!(bus.read(0x2006), 0xff);
+    }
+
+    #[test]
+    fn load_errs() {
+        let elf = Elf::parse(&build(false, true)).unwrap();
+        // Unmapped
+        let mut bus = Bus::new();
+        bus.map(0x1000, Ram::<0x100>::new().to_shared());
+        assert_eq!(elf.load(&mut bus), Err(BusError::Unmapped(0x2000)));
+        // Partially mapped
+        bus.map(0x2000, Ram::<0x4>::new().to_shared());
+        assert_eq!(elf.load(&mut bus), Err(BusError::Unmapped(0x2004)));
+        // Read-only (behind a translating adapter)
+        let rom = Rom::<0x100>::new().to_shared();
+        let mut bus = Bus::new();
+        bus.map(0x1000, Ram::<0x100>::new().to_shared());
+        bus.map(0x2000, Remap::new(-0x10, rom).to_shared());
+        assert_eq!(elf.load(&mut bus), Err(BusError::ReadOnly(0x2000)));
+        // Read-only (beneath the RAM)
+        let mut bus = Bus::new();
+        bus.map(0x1000, Ram::<0x100>::new().to_shared());
+        bus.map(0x2000, Ram::<0x4>::new().to_shared());
+        bus.map(0x2000, Rom::<0x100>::new().to_shared());
+        assert_eq!(elf.load(&mut bus), Err(BusError::ReadOnly(0x2004)));
+    }
+}
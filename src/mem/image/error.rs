@@ -0,0 +1,51 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::io;
+
+/// Image parsing error.
+///
+/// Each record-level variant holds the (1-based) line number of the offending
+/// record.
+#[derive(Debug)]
+pub enum ImageError {
+    /// The image could not be read.
+    Io(io::Error),
+    /// The image format could not be recognized.
+    Format,
+    /// The image structure is inconsistent or truncated.
+    Malformed,
+    /// The record is malformed.
+    Syntax(usize),
+    /// The record's checksum does not match its contents.
+    Checksum(usize),
+    /// The record type is not supported.
+    Record(usize),
+}
+
+impl Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read image: {err}"),
+            Self::Format => write!(f, "unrecognized image format"),
+            Self::Malformed => write!(f, "malformed image"),
+            Self::Syntax(line) => write!(f, "malformed record: line {line}"),
+            Self::Checksum(line) => write!(f, "checksum mismatch: line {line}"),
+            Self::Record(line) => write!(f, "unsupported record type: line {line}"),
+        }
+    }
+}
+
+impl Error for ImageError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ImageError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
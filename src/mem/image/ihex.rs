@@ -0,0 +1,118 @@
+use super::{decode, Image, ImageError};
+
+/// Parses an Intel HEX image.
+pub(super) fn parse(src: &str) -> Result<Image, ImageError> {
+    let mut image = Image::default();
+    // Extended (segment or linear) base address
+    let mut base = 0;
+
+    for (line, text) in (1..).zip(src.lines()) {
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        // Decode the record
+        let rec = text
+            .strip_prefix(':')
+            .and_then(decode)
+            .ok_or(ImageError::Syntax(line))?;
+        if rec.len() < 5 || rec.len() != 5 + rec[0] as usize {
+            return Err(ImageError::Syntax(line));
+        }
+        // Verify the checksum, such that all bytes sum to zero
+        if rec.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)) != 0 {
+            return Err(ImageError::Checksum(line));
+        }
+        let addr = u16::from_be_bytes([rec[1], rec[2]]) as usize;
+        let data = &rec[4..rec.len() - 1];
+        // Handle the record
+        match (rec[3], data) {
+            // Data
+            (0x00, _) => image.push(base + addr, data),
+            // End of file
+            (0x01, _) => break,
+            // Extended segment address
+            (0x02, &[hi, lo]) => base = (u16::from_be_bytes([hi, lo]) as usize) << 4,
+            // Start segment address
+            (0x03, &[cs0, cs1, ip0, ip1]) => {
+                let cs = u16::from_be_bytes([cs0, cs1]) as usize;
+                let ip = u16::from_be_bytes([ip0, ip1]) as usize;
+                image.entry = Some((cs << 4) + ip);
+            }
+            // Extended linear address
+            (0x04, &[hi, lo]) => base = (u16::from_be_bytes([hi, lo]) as usize) << 16,
+            // Start linear address
+            (0x05, &[b0, b1, b2, b3]) => {
+                image.entry = Some(u32::from_be_bytes([b0, b1, b2, b3]) as usize);
+            }
+            (0x02..=0x05, _) => return Err(ImageError::Syntax(line)),
+            _ => return Err(ImageError::Record(line)),
+        }
+    }
+
+    Ok(image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mem::image::Segment;
+
+    #[test]
+    fn parse_works() {
+        let image = parse(
+            "\
+            :0400000001020304F2\n\
+            :02000004000AF0\n\
+            :020010000506E3\n\
+            :040000050000800077\n\
+            :00000001FF\n\
+            :0100000000FF\n",
+        )
+        .unwrap();
+        assert_eq!(
+            image.segments(),
+            [
+                Segment {
+                    addr: 0x0000,
+                    data: vec![0x01, 0x02, 0x03, 0x04],
+                    fill: 0,
+                },
+                Segment {
+                    addr: 0xa0010,
+                    data: vec![0x05, 0x06],
+                    fill: 0,
+                },
+            ]
+        );
+        assert_eq!(image.entry(), Some(0x8000));
+    }
+
+    #[test]
+    fn parse_segment_works() {
+        let image = parse(
+            "\
+            :020000021000EC\n\
+            :01000100AA54\n\
+            :0400000300100020C9\n",
+        )
+        .unwrap();
+        assert_eq!(image.segments()[0].addr, 0x10001);
+        assert_eq!(image.entry(), Some(0x120));
+    }
+
+    #[test]
+    fn parse_errs() {
+        // Missing start code
+        assert!(matches!(parse("00000001FF"), Err(ImageError::Syntax(1))));
+        // Invalid length
+        assert!(matches!(
+            parse("\n:0200000001FE"),
+            Err(ImageError::Syntax(2))
+        ));
+        // Invalid checksum
+        assert!(matches!(parse(":00000001FE"), Err(ImageError::Checksum(1))));
+        // Unsupported record
+        assert!(matches!(parse(":00000006FA"), Err(ImageError::Record(1))));
+    }
+}
@@ -0,0 +1,241 @@
+//! Program image loading.
+//!
+//! # Usage
+//!
+//! An [`Image`] holds the segments and entry point of a program parsed from
+//! one of the following text formats, as commonly emitted by toolchains for
+//! 8-bit targets:
+//!
+//! - Intel HEX (`.hex`), via [`Image::from_ihex`].
+//! - Motorola S-record (`.srec`, `.s19`, etc.), via [`Image::from_srec`].
+//!
+//! Alternatively, the format may be detected automatically through
+//! [`FromStr`], [`Image::from_reader`], or [`Image::from_path`].
+//!
+//! Once parsed, an image can be loaded into any [`Device`] (including a
+//! [`Bus`](crate::bus::Bus)) with [`Image::load`], returning the entry point
+//! with which to start the CPU.
+//!
+//! ELF executables, as produced by GCC and similar toolchains, are instead
+//! parsed into an [`Elf`], which additionally exposes the executable's symbol
+//! table.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::bus::BusError;
+use crate::dev::Device;
+
+mod elf;
+mod error;
+mod ihex;
+mod srec;
+
+pub use self::elf::{Elf, Symbol};
+pub use self::error::ImageError;
+
+/// Parsed program image.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Image {
+    segments: Vec<Segment>,
+    entry: Option<usize>,
+}
+
+impl Image {
+    /// Parses an Intel HEX image.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any record is malformed, fails its checksum, or is
+    /// of an unsupported type.
+    pub fn from_ihex(src: &str) -> Result<Self, ImageError> {
+        ihex::parse(src)
+    }
+
+    /// Parses a Motorola S-record image.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any record is malformed, fails its checksum, or is
+    /// of an unsupported type.
+    pub fn from_srec(src: &str) -> Result<Self, ImageError> {
+        srec::parse(src)
+    }
+
+    /// Reads and parses an image of either format from `reader`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be read or parsed.
+    pub fn from_reader(mut reader: impl Read) -> Result<Self, ImageError> {
+        let mut src = String::new();
+        reader.read_to_string(&mut src)?;
+        src.parse()
+    }
+
+    /// Reads and parses an image of either format from the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ImageError> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// Gets the image's segments, in the order they were defined.
+    #[must_use]
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Gets the image's entry point, if specified.
+    #[must_use]
+    pub fn entry(&self) -> Option<usize> {
+        self.entry
+    }
+
+    /// Loads the image's segments into a device, returning the entry point.
+    ///
+    /// Segments are loaded with [`Device::poke`], such that images may be
+    /// loaded into read-only memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BusError::Unmapped`] at the first address not contained
+    /// by the device. Any preceding bytes will have already been loaded.
+    pub fn load<D>(&self, dev: &mut D) -> Result<Option<usize>, BusError>
+    where
+        D: Device + ?Sized,
+    {
+        for Segment { addr, data, fill } in &self.segments {
+            let zeros = (0..*fill).map(|_| 0);
+            for (index, byte) in (*addr..).zip(data.iter().copied().chain(zeros)) {
+                if !dev.contains(index) {
+                    return Err(BusError::Unmapped(index));
+                }
+                dev.poke(index, byte);
+            }
+        }
+        Ok(self.entry)
+    }
+
+    /// Appends data at `addr`, extending the last segment if contiguous.
+    fn push(&mut self, addr: usize, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        match self.segments.last_mut() {
+            Some(last) if last.addr + last.data.len() == addr => last.data.extend(data),
+            _ => self.segments.push(Segment {
+                addr,
+                data: data.to_vec(),
+                fill: 0,
+            }),
+        }
+    }
+}
+
+impl FromStr for Image {
+    type Err = ImageError;
+
+    /// Parses an image, detecting its format from the first record.
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let first = src.trim_start().chars().next();
+        match first {
+            Some(':') => Self::from_ihex(src),
+            Some('S') => Self::from_srec(src),
+            _ => Err(ImageError::Format),
+        }
+    }
+}
+
+/// Contiguous segment of an [`Image`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Segment {
+    /// Starting address.
+    pub addr: usize,
+    /// Segment contents.
+    pub data: Vec<u8>,
+    /// Number of zeroed bytes following the contents (e.g. BSS).
+    pub fill: usize,
+}
+
+/// Decodes a string of hexadecimal digit pairs.
+fn decode(hex: &str) -> Option<Vec<u8>> {
+    let digit = |byte: u8| char::from(byte).to_digit(16);
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| match *pair {
+            [hi, lo] => Some((digit(hi)? << 4 | digit(lo)?) as u8),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Bus;
+    use crate::mem::{Ram, Rom};
+
+    #[test]
+    fn from_str_works() {
+        let image: Image = ":01001000559A\n:00000001FF".parse().unwrap();
+        assert_eq!(image.segments()[0].addr, 0x0010);
+        let image: Image = "S1040010AA41\nS9030010EC".parse().unwrap();
+        assert_eq!(image.segments()[0].addr, 0x0010);
+        assert_eq!(image.entry(), Some(0x0010));
+    }
+
+    #[test]
+    fn from_str_errs() {
+        assert!(matches!("".parse::<Image>(), Err(ImageError::Format)));
+        assert!(matches!(
+            "\x7fELF".parse::<Image>(),
+            Err(ImageError::Format)
+        ));
+    }
+
+    #[test]
+    fn from_reader_works() {
+        let image = Image::from_reader(&b"S1040010AA41\nS9030010EC"[..]).unwrap();
+        assert_eq!(image.entry(), Some(0x0010));
+    }
+
+    #[test]
+    fn decode_works() {
+        assert_eq!(decode("00aBfF"), Some(vec![0x00, 0xab, 0xff]));
+        assert_eq!(decode("0"), None);
+        assert_eq!(decode("0g"), None);
+        assert_eq!(decode("+f"), None);
+        assert_eq!(decode("\u{e9}0"), None);
+    }
+
+    #[test]
+    fn load_works() {
+        let image = Image::from_srec("S1040010AA41\nS9030010EC").unwrap();
+        let mut rom = Rom::<0x100>::new();
+        assert_eq!(image.load(&mut rom), Ok(Some(0x0010)));
+        assert_eq!(rom[0x10], 0xaa);
+    }
+
+    #[test]
+    fn load_bus_works() {
+        let ram = Ram::<0x100>::new().to_shared();
+        let mut bus = Bus::new();
+        bus.map(0x8000, ram.clone());
+        let image = Image::from_ihex(":0280100001026B\n:00000001FF").unwrap();
+        assert_eq!(image.load(&mut bus), Ok(None));
+        assert_eq!(bus.read_u16_be(0x8010), 0x0102);
+    }
+
+    #[test]
+    fn load_errs() {
+        let image = Image::from_ihex(":0200FF000102FC\n:00000001FF").unwrap();
+        let mut ram = Ram::<0x100>::new();
+        assert_eq!(image.load(&mut ram), Err(BusError::Unmapped(0x100)));
+        assert_eq!(ram[0xff], 0x01);
+    }
+}
@@ -0,0 +1,94 @@
+use super::{decode, Image, ImageError};
+
+/// Parses a Motorola S-record image.
+pub(super) fn parse(src: &str) -> Result<Image, ImageError> {
+    let mut image = Image::default();
+
+    for (line, text) in (1..).zip(src.lines()) {
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        // Determine the record type
+        let rest = text.strip_prefix('S').ok_or(ImageError::Syntax(line))?;
+        let kind = rest.chars().next().ok_or(ImageError::Syntax(line))?;
+        let width = match kind {
+            '0' | '1' | '5' | '9' => 2,
+            '2' | '6' | '8' => 3,
+            '3' | '7' => 4,
+            _ => return Err(ImageError::Record(line)),
+        };
+        // Decode the record
+        let rec = decode(&rest[1..]).ok_or(ImageError::Syntax(line))?;
+        if rec.len() < 2 + width || rec.len() != 1 + rec[0] as usize {
+            return Err(ImageError::Syntax(line));
+        }
+        // Verify the checksum, such that all bytes sum to 0xff
+        if rec.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)) != 0xff {
+            return Err(ImageError::Checksum(line));
+        }
+        let addr = rec[1..=width]
+            .iter()
+            .fold(0, |addr, &byte| addr << 8 | byte as usize);
+        let data = &rec[1 + width..rec.len() - 1];
+        // Handle the record
+        match kind {
+            // Data
+            '1' | '2' | '3' => image.push(addr, data),
+            // Start address
+            '7' | '8' | '9' => image.entry = Some(addr),
+            // Header and record counts
+            _ => (),
+        }
+    }
+
+    Ok(image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mem::image::Segment;
+
+    #[test]
+    fn parse_works() {
+        let image = parse(
+            "\
+            S00600004844521B\n\
+            S107000001020304EE\n\
+            S107000405060708DA\n\
+            S2060100100506DD\n\
+            S5030003F9\n\
+            S9030100FB\n",
+        )
+        .unwrap();
+        assert_eq!(
+            image.segments(),
+            [
+                Segment {
+                    addr: 0x0000,
+                    data: vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08],
+                    fill: 0,
+                },
+                Segment {
+                    addr: 0x10010,
+                    data: vec![0x05, 0x06],
+                    fill: 0,
+                },
+            ]
+        );
+        assert_eq!(image.entry(), Some(0x0100));
+    }
+
+    #[test]
+    fn parse_errs() {
+        // Missing start code
+        assert!(matches!(parse("9030000FC"), Err(ImageError::Syntax(1))));
+        // Invalid length
+        assert!(matches!(parse("S9040000FB"), Err(ImageError::Syntax(1))));
+        // Invalid checksum
+        assert!(matches!(parse("S9030000FB"), Err(ImageError::Checksum(1))));
+        // Unsupported record
+        assert!(matches!(parse("S4030000FC"), Err(ImageError::Record(1))));
+    }
+}
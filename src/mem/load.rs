@@ -0,0 +1,160 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Size policy for loading memory images.
+///
+/// Determines how an image whose size differs from that of the memory it is
+/// loaded into is handled.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Fit {
+    /// Images must be exactly the size of the memory.
+    #[default]
+    Exact,
+    /// Smaller images are padded with the provided byte.
+    Pad(u8),
+    /// Larger images are truncated.
+    Truncate,
+    /// Smaller images are padded with the provided byte, and larger images
+    /// are truncated.
+    Resize(u8),
+}
+
+/// Memory image loading error.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The image could not be read.
+    Io(io::Error),
+    /// The image size is not permitted by the [`Fit`] policy.
+    Size {
+        /// Size of the memory, in bytes.
+        expected: usize,
+        /// Size of the image, in bytes.
+        found: usize,
+    },
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read image: {err}"),
+            Self::Size { expected, found } => write!(
+                f,
+                "image size mismatch: expected {expected:#x} bytes, found {found:#x}"
+            ),
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Size { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for LoadError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Reads an image from `reader`, fitting it to `len` bytes according to the
+/// provided policy.
+pub(super) fn read(mut reader: impl Read, len: usize, fit: Fit) -> Result<Vec<u8>, LoadError> {
+    let mut buf = Vec::with_capacity(len);
+    reader.read_to_end(&mut buf)?;
+    let found = buf.len();
+    match fit {
+        Fit::Pad(byte) | Fit::Resize(byte) if found < len => buf.resize(len, byte),
+        Fit::Truncate | Fit::Resize(_) if found > len => buf.truncate(len),
+        _ if found != len => {
+            return Err(LoadError::Size {
+                expected: len,
+                found,
+            })
+        }
+        _ => (),
+    }
+    Ok(buf)
+}
+
+/// Reads an entire image from `reader`.
+pub(super) fn read_all(mut reader: impl Read) -> Result<Vec<u8>, LoadError> {
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+/// Opens the file at `path` for reading.
+pub(super) fn open(path: impl AsRef<Path>) -> Result<File, LoadError> {
+    Ok(File::open(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_exact_works() {
+        assert_eq!(read(&[1, 2, 3][..], 3, Fit::Exact).unwrap(), [1, 2, 3]);
+        assert!(matches!(
+            read(&[1, 2][..], 3, Fit::Exact),
+            Err(LoadError::Size {
+                expected: 3,
+                found: 2
+            })
+        ));
+        assert!(matches!(
+            read(&[1, 2, 3, 4][..], 3, Fit::Exact),
+            Err(LoadError::Size {
+                expected: 3,
+                found: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn read_pad_works() {
+        assert_eq!(read(&[1][..], 3, Fit::Pad(0xff)).unwrap(), [1, 0xff, 0xff]);
+        assert!(read(&[1, 2, 3, 4][..], 3, Fit::Pad(0xff)).is_err());
+    }
+
+    #[test]
+    fn read_truncate_works() {
+        assert_eq!(
+            read(&[1, 2, 3, 4][..], 3, Fit::Truncate).unwrap(),
+            [1, 2, 3]
+        );
+        assert!(read(&[1][..], 3, Fit::Truncate).is_err());
+    }
+
+    #[test]
+    fn read_resize_works() {
+        assert_eq!(read(&[1][..], 2, Fit::Resize(0)).unwrap(), [1, 0]);
+        assert_eq!(read(&[1, 2, 3][..], 2, Fit::Resize(0)).unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn open_errs() {
+        let err = open("/nonexistent/image.bin").unwrap_err();
+        assert!(matches!(err, LoadError::Io(_)));
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn display_works() {
+        let err = LoadError::Size {
+            expected: 0x100,
+            found: 0x80,
+        };
+        assert_eq!(
+            err.to_string(),
+            "image size mismatch: expected 0x100 bytes, found 0x80"
+        );
+    }
+}
@@ -9,17 +9,38 @@
 //!
 //! Additionally, both models implement [`Device`](crate::dev::Device), allowing
 //! them to be mapped to another address space.
+//!
+//! Where the size of a memory is only known at runtime (e.g. from a cartridge
+//! header), the [`DynRam`] and [`DynRom`] models may be used instead.
+//!
+//! Contents of each model can be loaded from a reader or file (e.g.
+//! [`Rom::from_path`]), with mismatched image sizes handled according to a
+//! [`Fit`] policy. Program images in other formats (e.g. Intel HEX) are
+//! handled by the [`image`] module.
+//!
+//! Battery-backed memories, whose contents persist across restarts, are
+//! modelled by [`SaveRam`].
 
 use std::fmt::Display;
 use std::ops::Deref;
 
 use crate::blk::Block;
 
+mod dyn_ram;
+mod dyn_rom;
+mod load;
 mod ram;
 mod rom;
+mod save;
+
+pub mod image;
 
+pub use self::dyn_ram::DynRam;
+pub use self::dyn_rom::DynRom;
+pub use self::load::{Fit, LoadError};
 pub use self::ram::Ram;
 pub use self::rom::Rom;
+pub use self::save::SaveRam;
 
 /// Generic memory model.
 ///
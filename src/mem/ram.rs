@@ -1,7 +1,10 @@
 use std::fmt::{Debug, Display};
+use std::io::Read;
 use std::ops::{Deref, DerefMut};
+use std::path::Path;
 
 use crate::blk::Block;
+use crate::mem::load::{self, Fit, LoadError};
 use crate::mem::Memory;
 
 /// Random-access memory model.
@@ -13,6 +16,27 @@ impl<const N: usize> Ram<N> {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Constructs a `Ram<N>` from an image read from `reader`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be read, or if its size is not
+    /// permitted by the provided [`Fit`] policy.
+    pub fn from_reader(reader: impl Read, fit: Fit) -> Result<Self, LoadError> {
+        let buf = load::read(reader, N, fit)?;
+        Ok(Self(buf.into_boxed_slice().try_into().unwrap()))
+    }
+
+    /// Constructs a `Ram<N>` from an image read from the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or if its size is not
+    /// permitted by the provided [`Fit`] policy.
+    pub fn from_path(path: impl AsRef<Path>, fit: Fit) -> Result<Self, LoadError> {
+        Self::from_reader(load::open(path)?, fit)
+    }
 }
 
 impl<const N: usize> Block for Ram<N> {
@@ -85,6 +109,15 @@ mod tests {
         assert_eq!(*ram, buf);
     }
 
+    #[test]
+    fn from_reader_works() {
+        let ram = Ram::<0x4>::from_reader(&[0xaa; 3][..], Fit::Resize(0)).unwrap();
+        assert_eq!(*ram, [0xaa, 0xaa, 0xaa, 0x00]);
+        let ram = Ram::<0x4>::from_reader(&[0xaa; 5][..], Fit::Resize(0)).unwrap();
+        assert_eq!(*ram, [0xaa; 4]);
+        assert!(Ram::<0x4>::from_reader(&[0xaa; 5][..], Fit::Exact).is_err());
+    }
+
     #[test]
     fn device_contains_works() {
         const N0: usize = 0x0;
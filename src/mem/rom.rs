@@ -1,13 +1,22 @@
 use std::fmt::{Debug, Display};
+use std::io::Read;
 use std::ops::Deref;
+use std::path::Path;
 
 use crate::blk::Block;
+use crate::bus::BusError;
 use crate::dev::Device;
+use crate::mem::load::{self, Fit, LoadError};
 use crate::mem::Memory;
 
 /// Read-only memory model.
 ///
-/// Panics on [`Device::write`].
+/// Panics on [`Device::write`], and returns [`BusError::ReadOnly`] on
+/// [`Device::try_write`]. Contents may still be patched (e.g. by a debugger)
+/// through [`Device::poke`].
+///
+/// To ignore or redirect writes instead, wrap it in a
+/// [`ReadOnly`](crate::bus::adapt::ReadOnly) adapter.
 #[derive(Debug)]
 pub struct Rom<const N: usize>(Box<[u8; N]>);
 
@@ -16,6 +25,27 @@ impl<const N: usize> Rom<N> {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Constructs a `Rom<N>` from an image read from `reader`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be read, or if its size is not
+    /// permitted by the provided [`Fit`] policy.
+    pub fn from_reader(reader: impl Read, fit: Fit) -> Result<Self, LoadError> {
+        let buf = load::read(reader, N, fit)?;
+        Ok(Self(buf.into_boxed_slice().try_into().unwrap()))
+    }
+
+    /// Constructs a `Rom<N>` from an image read from the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or if its size is not
+    /// permitted by the provided [`Fit`] policy.
+    pub fn from_path(path: impl AsRef<Path>, fit: Fit) -> Result<Self, LoadError> {
+        Self::from_reader(load::open(path)?, fit)
+    }
 }
 
 impl<const N: usize> Block for Rom<N> {}
@@ -50,16 +80,32 @@ impl<const N: usize> Device for Rom<N> {
         <[u8]>::len(self)
     }
 
-    fn read(&self, index: usize) -> u8 {
+    fn peek(&self, index: usize) -> u8 {
         self[index]
     }
 
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        buf.copy_from_slice(&self[index..index + buf.len()]);
+    }
+
     /// # Panics
     ///
     /// Panics when attempting to write to a [`Rom`].
     fn write(&mut self, _index: usize, _value: u8) {
         panic!("called `Device::write()` on a `Rom`");
     }
+
+    fn try_write(&mut self, index: usize, _value: u8) -> Result<(), BusError> {
+        if self.contains(index) {
+            Err(BusError::ReadOnly(index))
+        } else {
+            Err(BusError::OutOfBounds(index))
+        }
+    }
+
+    fn poke(&mut self, index: usize, value: u8) {
+        self.0[index] = value;
+    }
 }
 
 impl<const N: usize> Display for Rom<N> {
@@ -99,6 +145,37 @@ mod tests {
         assert_eq!(*rom, buf);
     }
 
+    #[test]
+    fn from_reader_works() {
+        let rom = Rom::<0x4>::from_reader(&[0xaa; 4][..], Fit::Exact).unwrap();
+        assert_eq!(*rom, [0xaa; 4]);
+        let rom = Rom::<0x4>::from_reader(&[0xaa; 2][..], Fit::Pad(0xff)).unwrap();
+        assert_eq!(*rom, [0xaa, 0xaa, 0xff, 0xff]);
+        let rom = Rom::<0x4>::from_reader(&[0xaa; 8][..], Fit::Truncate).unwrap();
+        assert_eq!(*rom, [0xaa; 4]);
+    }
+
+    #[test]
+    fn from_reader_errs() {
+        assert!(matches!(
+            Rom::<0x4>::from_reader(&[0xaa; 2][..], Fit::Exact),
+            Err(LoadError::Size {
+                expected: 4,
+                found: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn from_path_works() {
+        let pid = std::process::id();
+        let path = std::env::temp_dir().join(format!("remus-{pid}-rom-from-path.bin"));
+        std::fs::write(&path, [0x01, 0x23, 0x45, 0x67]).unwrap();
+        let rom = Rom::<0x4>::from_path(&path, Fit::Exact);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(*rom.unwrap(), [0x01, 0x23, 0x45, 0x67]);
+    }
+
     #[test]
     fn device_contains_works() {
         const N0: usize = 0x0;
@@ -138,14 +215,37 @@ mod tests {
 
     #[test]
     fn device_read_works() {
-        let rom = Rom::<0x1>::from(&[0xaa]);
+        let mut rom = Rom::<0x1>::from(&[0xaa]);
         assert_eq!(rom.read(0x0), 0xaa);
     }
 
+    #[test]
+    fn device_read_word_works() {
+        let mut rom = Rom::<0x4>::from(&[0x01, 0x23, 0x45, 0x67]);
+        assert_eq!(rom.read_u32_le(0x0), 0x67452301);
+        assert_eq!(rom.read_u16_be(0x2), 0x4567);
+    }
+
     #[test]
     #[should_panic]
     fn device_write_panics() {
         let mut rom = Rom::<0x1>::from(&[0xaa]);
         rom.write(0x0, 0xaa);
     }
+
+    #[test]
+    fn device_poke_works() {
+        let mut rom = Rom::<0x1>::from(&[0xaa]);
+        rom.poke(0x0, 0xbb);
+        assert_eq!(rom.peek(0x0), 0xbb);
+        assert_eq!(rom.read(0x0), 0xbb);
+    }
+
+    #[test]
+    fn device_try_write_errs() {
+        let mut rom = Rom::<0x1>::from(&[0xaa]);
+        assert_eq!(rom.try_write(0x0, 0xbb), Err(BusError::ReadOnly(0x0)));
+        assert_eq!(rom.try_write(0x1, 0xbb), Err(BusError::OutOfBounds(0x1)));
+        assert_eq!(rom.read(0x0), 0xaa);
+    }
 }
@@ -0,0 +1,284 @@
+use std::fmt::{Debug, Display};
+use std::fs::{self, File};
+use std::io::{self, ErrorKind, Write};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::blk::Block;
+use crate::dev::Device;
+use crate::mem::load::{self, Fit, LoadError};
+use crate::mem::Memory;
+
+/// Battery-backed save memory model.
+///
+/// # Usage
+///
+/// The `SaveRam` model behaves like a [`DynRam`](super::DynRam) whose
+/// contents persist across emulator restarts, as with cartridge SRAM. It is
+/// loaded from a save file when opened, and tracks whether it has been written
+/// to (i.e. is dirty) since last being flushed back to that file.
+///
+/// Dirty contents are flushed:
+/// - On demand, with [`SaveRam::flush`].
+/// - Once no writes have occurred for a debounce interval (see
+///   [`SaveRam::debounce`]), when checked with [`SaveRam::poll`]. Emulators
+///   should poll periodically, such as once per frame.
+/// - When dropped. As errors cannot be reported at this point, emulators
+///   should prefer to flush explicitly before exiting.
+///
+/// As its contents are battery-backed, they are left intact by
+/// [`Block::reset`].
+#[derive(Debug)]
+pub struct SaveRam {
+    mem: Box<[u8]>,
+    path: PathBuf,
+    dirty: Option<Instant>,
+    debounce: Option<Duration>,
+}
+
+impl SaveRam {
+    /// Opens a `SaveRam` of `len` bytes, backed by the file at `path`.
+    ///
+    /// If the file does not yet exist, the memory is initialized to zeros,
+    /// and the file is created when first flushed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read, or if its size
+    /// is not permitted by the provided [`Fit`] policy.
+    pub fn open(path: impl AsRef<Path>, len: usize, fit: Fit) -> Result<Self, LoadError> {
+        let path = path.as_ref().to_path_buf();
+        let mem = match File::open(&path) {
+            Ok(file) => load::read(file, len, fit)?,
+            Err(err) if err.kind() == ErrorKind::NotFound => vec![Default::default(); len],
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self {
+            mem: mem.into_boxed_slice(),
+            path,
+            dirty: None,
+            debounce: None,
+        })
+    }
+
+    /// Flushes automatically once no writes have occurred for `interval`.
+    ///
+    /// See [`SaveRam::poll`].
+    #[must_use]
+    pub fn debounce(mut self, interval: Duration) -> Self {
+        self.debounce = Some(interval);
+        self
+    }
+
+    /// Gets the path of the backing save file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Checks if the contents have changed since last being flushed.
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.is_some()
+    }
+
+    /// Writes dirty contents to the backing save file.
+    ///
+    /// Contents are first written to a temporary sibling file, which then
+    /// replaces the save file, such that an interrupted flush never corrupts
+    /// an existing save.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written, in which case the
+    /// contents remain dirty.
+    pub fn flush(&mut self) -> io::Result<()> {
+        if self.dirty.is_some() {
+            let mut tmp = self.path.clone().into_os_string();
+            tmp.push(".tmp");
+            let mut file = File::create(&tmp)?;
+            file.write_all(&self.mem)?;
+            file.sync_all()?;
+            fs::rename(&tmp, &self.path)?;
+            self.dirty = None;
+        }
+        Ok(())
+    }
+
+    /// Flushes dirty contents if the debounce interval has elapsed since the
+    /// most recent write, returning whether a flush occurred.
+    ///
+    /// Does nothing if no debounce interval has been set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn poll(&mut self) -> io::Result<bool> {
+        match (self.dirty, self.debounce) {
+            (Some(since), Some(interval)) if since.elapsed() >= interval => {
+                self.flush().map(|()| true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+impl Block for SaveRam {}
+
+impl Memory for SaveRam {}
+
+impl Deref for SaveRam {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.mem
+    }
+}
+
+impl Device for SaveRam {
+    fn contains(&self, index: usize) -> bool {
+        (0..self.len()).contains(&index)
+    }
+
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+
+    fn peek(&self, index: usize) -> u8 {
+        self[index]
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        self.mem[index] = value;
+        self.dirty = Some(Instant::now());
+    }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        buf.copy_from_slice(&self[index..index + buf.len()]);
+    }
+
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        self.mem[index..index + buf.len()].copy_from_slice(buf);
+        self.dirty = Some(Instant::now());
+    }
+}
+
+impl Display for SaveRam {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self as &dyn Memory)
+    }
+}
+
+impl Drop for SaveRam {
+    fn drop(&mut self) {
+        // Errors cannot be reported here, so are ignored
+        let _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gets a save file path for a test, unique to this process.
+    fn path(name: &str) -> PathBuf {
+        let pid = std::process::id();
+        let path = std::env::temp_dir().join(format!("remus-{pid}-{name}.sav"));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn open_works() {
+        let path = path("open-works");
+        // Missing files are zeroed
+        let ram = SaveRam::open(&path, 0x10, Fit::Exact).unwrap();
+        assert_eq!(*ram, [0; 0x10]);
+        assert!(!ram.is_dirty());
+        drop(ram);
+        // Clean memories are not written
+        assert!(!path.exists());
+        // Existing files are loaded
+        fs::write(&path, [0xaa; 0x10]).unwrap();
+        let ram = SaveRam::open(&path, 0x10, Fit::Exact).unwrap();
+        assert_eq!(*ram, [0xaa; 0x10]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn open_errs() {
+        let path = path("open-errs");
+        fs::write(&path, [0xaa; 0x8]).unwrap();
+        assert!(matches!(
+            SaveRam::open(&path, 0x10, Fit::Exact),
+            Err(LoadError::Size {
+                expected: 0x10,
+                found: 0x8
+            })
+        ));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn flush_works() {
+        let path = path("flush-works");
+        let mut ram = SaveRam::open(&path, 0x4, Fit::Exact).unwrap();
+        ram.write_u16_le(0x0, 0xbbaa);
+        assert!(ram.is_dirty());
+        ram.flush().unwrap();
+        assert!(!ram.is_dirty());
+        assert_eq!(fs::read(&path).unwrap(), [0xaa, 0xbb, 0x00, 0x00]);
+        // Existing saves are replaced, leaving no temporary file behind
+        ram.write(0x3, 0xcc);
+        ram.flush().unwrap();
+        assert_eq!(fs::read(&path).unwrap(), [0xaa, 0xbb, 0x00, 0xcc]);
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(".tmp");
+        assert!(!Path::new(&tmp).exists());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn drop_works() {
+        let path = path("drop-works");
+        let mut ram = SaveRam::open(&path, 0x4, Fit::Exact).unwrap();
+        ram.write(0x3, 0xcc);
+        drop(ram);
+        let ram = SaveRam::open(&path, 0x4, Fit::Exact).unwrap();
+        assert_eq!(ram.peek(0x3), 0xcc);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn poll_works() {
+        let path = path("poll-works");
+        // Without debounce
+        let mut ram = SaveRam::open(&path, 0x4, Fit::Exact).unwrap();
+        ram.write(0x0, 0xaa);
+        assert!(!ram.poll().unwrap());
+        assert!(ram.is_dirty());
+        // Before interval has elapsed
+        let mut ram = ram.debounce(Duration::from_secs(3600));
+        assert!(!ram.poll().unwrap());
+        assert!(ram.is_dirty());
+        // After interval has elapsed
+        let mut ram = ram.debounce(Duration::ZERO);
+        assert!(ram.poll().unwrap());
+        assert!(!ram.is_dirty());
+        assert!(!ram.poll().unwrap());
+        assert_eq!(fs::read(&path).unwrap(), [0xaa, 0x00, 0x00, 0x00]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn block_reset_works() {
+        let path = path("block-reset-works");
+        let mut ram = SaveRam::open(&path, 0x4, Fit::Exact).unwrap();
+        ram.write(0x0, 0xaa);
+        ram.reset();
+        assert_eq!(ram.peek(0x0), 0xaa);
+        drop(ram);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -11,7 +11,8 @@
 //!
 //! Since `Register` implements [`Device`], it may be mapped to another address
 //! space using a [`Bus`](crate::bus::Bus), and is [byte-addressable] through
-//! [`Device::read`] and [`Device::write`].
+//! [`Device::read`] and [`Device::write`]. Wide accesses (e.g.
+//! [`Device::read_u16_le`]) are served natively.
 //!
 //! [newtype pattern]:  https://doc.rust-lang.org/rust-by-example/generics/new_types.html
 //! [byte-addressable]: https://en.wikipedia.org/wiki/Byte_addressing
@@ -77,7 +78,7 @@ impl Device for Register<u8> {
         std::mem::size_of::<Self>()
     }
 
-    fn read(&self, index: usize) -> u8 {
+    fn peek(&self, index: usize) -> u8 {
         self.to_le_bytes()[index]
     }
 
@@ -86,6 +87,16 @@ impl Device for Register<u8> {
         bytes[index] = value;
         self.0 = u8::from_le_bytes(bytes);
     }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        buf.copy_from_slice(&self.to_le_bytes()[index..index + buf.len()]);
+    }
+
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        let mut bytes = self.to_le_bytes();
+        bytes[index..index + buf.len()].copy_from_slice(buf);
+        self.0 = u8::from_le_bytes(bytes);
+    }
 }
 
 impl Device for Register<u16> {
@@ -97,7 +108,7 @@ impl Device for Register<u16> {
         std::mem::size_of::<Self>()
     }
 
-    fn read(&self, index: usize) -> u8 {
+    fn peek(&self, index: usize) -> u8 {
         self.to_le_bytes()[index]
     }
 
@@ -106,6 +117,16 @@ impl Device for Register<u16> {
         bytes[index] = value;
         self.0 = u16::from_le_bytes(bytes);
     }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        buf.copy_from_slice(&self.to_le_bytes()[index..index + buf.len()]);
+    }
+
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        let mut bytes = self.to_le_bytes();
+        bytes[index..index + buf.len()].copy_from_slice(buf);
+        self.0 = u16::from_le_bytes(bytes);
+    }
 }
 
 impl Device for Register<u32> {
@@ -117,7 +138,7 @@ impl Device for Register<u32> {
         std::mem::size_of::<Self>()
     }
 
-    fn read(&self, index: usize) -> u8 {
+    fn peek(&self, index: usize) -> u8 {
         self.to_le_bytes()[index]
     }
 
@@ -126,6 +147,16 @@ impl Device for Register<u32> {
         bytes[index] = value;
         self.0 = u32::from_le_bytes(bytes);
     }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        buf.copy_from_slice(&self.to_le_bytes()[index..index + buf.len()]);
+    }
+
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        let mut bytes = self.to_le_bytes();
+        bytes[index..index + buf.len()].copy_from_slice(buf);
+        self.0 = u32::from_le_bytes(bytes);
+    }
 }
 
 impl Device for Register<u64> {
@@ -137,7 +168,7 @@ impl Device for Register<u64> {
         std::mem::size_of::<Self>()
     }
 
-    fn read(&self, index: usize) -> u8 {
+    fn peek(&self, index: usize) -> u8 {
         self.to_le_bytes()[index]
     }
 
@@ -146,6 +177,16 @@ impl Device for Register<u64> {
         bytes[index] = value;
         self.0 = u64::from_le_bytes(bytes);
     }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        buf.copy_from_slice(&self.to_le_bytes()[index..index + buf.len()]);
+    }
+
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        let mut bytes = self.to_le_bytes();
+        bytes[index..index + buf.len()].copy_from_slice(buf);
+        self.0 = u64::from_le_bytes(bytes);
+    }
 }
 
 impl Device for Register<u128> {
@@ -157,7 +198,7 @@ impl Device for Register<u128> {
         std::mem::size_of::<Self>()
     }
 
-    fn read(&self, index: usize) -> u8 {
+    fn peek(&self, index: usize) -> u8 {
         self.to_le_bytes()[index]
     }
 
@@ -166,11 +207,22 @@ impl Device for Register<u128> {
         bytes[index] = value;
         self.0 = u128::from_le_bytes(bytes);
     }
+
+    fn read_bytes(&mut self, index: usize, buf: &mut [u8]) {
+        buf.copy_from_slice(&self.to_le_bytes()[index..index + buf.len()]);
+    }
+
+    fn write_bytes(&mut self, index: usize, buf: &[u8]) {
+        let mut bytes = self.to_le_bytes();
+        bytes[index..index + buf.len()].copy_from_slice(buf);
+        self.0 = u128::from_le_bytes(bytes);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dev::{DeviceExt, Endian};
 
     #[test]
     fn new_works() {
@@ -290,23 +342,23 @@ mod tests {
     #[test]
     fn device_read_works() {
         // 8-bit register
-        let r8 = Register::<u8>::from(0x01_u8);
+        let mut r8 = Register::<u8>::from(0x01_u8);
         assert_eq!(r8.read(0), 0x01);
 
         // 16-bit register
-        let r16 = Register::<u16>::from(0x0123_u16);
+        let mut r16 = Register::<u16>::from(0x0123_u16);
         assert_eq!(r16.read(1), 0x01);
 
         // 32-bit register
-        let r32 = Register::<u32>::from(0x01234567_u32);
+        let mut r32 = Register::<u32>::from(0x01234567_u32);
         assert_eq!(r32.read(2), 0x23);
 
         // 64-bit register
-        let r64 = Register::<u64>::from(0x0123456789abcdef_u64);
+        let mut r64 = Register::<u64>::from(0x0123456789abcdef_u64);
         assert_eq!(r64.read(4), 0x67);
 
         // 128-bit register
-        let r128 = Register::<u128>::from(0x0123456789abcdef0123456789abcdef_u128);
+        let mut r128 = Register::<u128>::from(0x0123456789abcdef0123456789abcdef_u128);
         assert_eq!(r128.read(8), 0xef);
     }
 
@@ -337,4 +389,42 @@ mod tests {
         r128.write(8, 0xee);
         assert_eq!(*r128, 0x00000000000000ee0000000000000000_u128);
     }
+
+    #[test]
+    fn device_read_word_works() {
+        // 16-bit register
+        let mut r16 = Register::<u16>::from(0x0123_u16);
+        assert_eq!(r16.read_u16_le(0), 0x0123);
+        assert_eq!(r16.read_u16_be(0), 0x2301);
+
+        // 32-bit register
+        let mut r32 = Register::<u32>::from(0x01234567_u32);
+        assert_eq!(r32.read_u16_le(2), 0x0123);
+        assert_eq!(r32.read_u32_le(0), 0x01234567);
+
+        // 128-bit register
+        let mut r128 = Register::<u128>::from(0x0123456789abcdef0123456789abcdef_u128);
+        assert_eq!(
+            r128.read_word::<u128>(0, Endian::Little),
+            0x0123456789abcdef0123456789abcdef_u128
+        );
+    }
+
+    #[test]
+    fn device_write_word_works() {
+        // 16-bit register
+        let mut r16 = Register::<u16>::new();
+        r16.write_u16_be(0, 0x0123);
+        assert_eq!(*r16, 0x2301_u16);
+
+        // 64-bit register
+        let mut r64 = Register::<u64>::new();
+        r64.write_u32_le(4, 0x01234567);
+        assert_eq!(*r64, 0x0123456700000000_u64);
+
+        // 128-bit register
+        let mut r128 = Register::<u128>::new();
+        r128.write_word(0, 0x0123456789abcdef0123456789abcdef_u128, Endian::Big);
+        assert_eq!(*r128, 0xefcdab8967452301efcdab8967452301_u128);
+    }
 }